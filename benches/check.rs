@@ -0,0 +1,85 @@
+//! Compares the vectorised passes over large requests with their scalar fallbacks, and measures
+//! `try_indices_slice` for the same requests. Run with
+//! `cargo bench --bench check --features bench-internals`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use indices::simd;
+use std::hint::black_box;
+
+const LENS: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// `len` distinct indices in a fixed pseudo random order.
+fn shuffled(len: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for i in (1..len).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        indices.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    indices
+}
+
+fn passes(c: &mut Criterion) {
+    for len in LENS {
+        let sorted: Vec<usize> = (0..len).collect();
+        let shuffled = shuffled(len);
+
+        let mut group = c.benchmark_group("max_index");
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("simd", len), &shuffled, |b, indices| {
+            b.iter(|| simd::max_index(black_box(indices)))
+        });
+        group.bench_with_input(BenchmarkId::new("scalar", len), &shuffled, |b, indices| {
+            b.iter(|| simd::scalar::max_index(black_box(indices)))
+        });
+        group.finish();
+
+        let mut group = c.benchmark_group("is_strictly_increasing");
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("simd", len), &sorted, |b, indices| {
+            b.iter(|| simd::is_strictly_increasing(black_box(indices)))
+        });
+        group.bench_with_input(BenchmarkId::new("scalar", len), &sorted, |b, indices| {
+            b.iter(|| simd::scalar::is_strictly_increasing(black_box(indices)))
+        });
+        group.finish();
+
+        let mut group = c.benchmark_group("find_adjacent_equal");
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("simd", len), &sorted, |b, indices| {
+            b.iter(|| simd::find_adjacent_equal(black_box(indices)))
+        });
+        group.bench_with_input(BenchmarkId::new("scalar", len), &sorted, |b, indices| {
+            b.iter(|| simd::scalar::find_adjacent_equal(black_box(indices)))
+        });
+        group.finish();
+    }
+}
+
+fn indices_slice(c: &mut Criterion) {
+    let mut group = c.benchmark_group("try_indices_slice");
+    for len in LENS {
+        let mut data = vec![0u64; len];
+        let sorted: Vec<usize> = (0..len).collect();
+        let shuffled = shuffled(len);
+        // Sparse, so the bitset is skipped and a copy of the indices is sorted.
+        let mut sparse_data = vec![0u64; len * 1_000];
+        let sparse: Vec<usize> = shuffled.iter().map(|index| index * 1_000).collect();
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("sorted", len), &sorted, |b, indices| {
+            b.iter(|| indices::try_indices_slice(&mut data, black_box(indices)).is_ok())
+        });
+        group.bench_with_input(BenchmarkId::new("dense", len), &shuffled, |b, indices| {
+            b.iter(|| indices::try_indices_slice(&mut data, black_box(indices)).is_ok())
+        });
+        group.bench_with_input(BenchmarkId::new("sparse", len), &sparse, |b, indices| {
+            b.iter(|| indices::try_indices_slice(&mut sparse_data, black_box(indices)).is_ok())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, passes, indices_slice);
+criterion_main!(benches);
@@ -0,0 +1,286 @@
+//! Versions of the `indices!` family of macros that reject invalid literal arguments at compile
+//! time. Negative, duplicate or overlapping literal indices and ranges are compile errors, as are
+//! unsorted literals for the `_ordered` macros. After the check, each macro expands to the macro of
+//! the same name in `indices`, so arguments that are not literals are checked at runtime as usual.
+//! Once any argument is marked `mut`, only overlaps with a `mut` argument are errors, as for the
+//! `indices` macros.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Expr, ExprLit, ExprRange, Lit, RangeLimits, Token, UnOp,
+};
+
+/// Same as `indices::indices!`, but rejects negative, duplicate or overlapping literal indices at
+/// compile time.
+#[proc_macro]
+pub fn indices(input: TokenStream) -> TokenStream {
+    expand(input.into(), quote!(::indices::indices), false).into()
+}
+
+/// Same as `indices::try_indices!`, but rejects negative, duplicate or overlapping literal indices
+/// at compile time.
+#[proc_macro]
+pub fn try_indices(input: TokenStream) -> TokenStream {
+    expand(input.into(), quote!(::indices::try_indices), false).into()
+}
+
+/// Same as `indices::indices_ordered!`, but rejects negative, duplicate, overlapping or unsorted
+/// literal indices at compile time.
+#[proc_macro]
+pub fn indices_ordered(input: TokenStream) -> TokenStream {
+    expand(input.into(), quote!(::indices::indices_ordered), true).into()
+}
+
+/// Same as `indices::try_indices_ordered!`, but rejects negative, duplicate, overlapping or
+/// unsorted literal indices at compile time.
+#[proc_macro]
+pub fn try_indices_ordered(input: TokenStream) -> TokenStream {
+    expand(input.into(), quote!(::indices::try_indices_ordered), true).into()
+}
+
+//************************************************************************//
+
+fn expand(input: TokenStream2, target: TokenStream2, ordered: bool) -> TokenStream2 {
+    match check(input.clone(), ordered) {
+        Ok(()) => quote!(#target!(#input)),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// The elements covered by an argument whose bounds are all literals. `start..end`, where a single
+/// index `i` is `i..i + 1` and an end of `None` is the end of the slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Known {
+    start: u128,
+    end: Option<u128>,
+}
+
+impl Known {
+    /// Returns the smallest index covered by both arguments. An index covered by a bounded argument
+    /// is in every unbounded argument starting at or before it, or the bounded argument is out of
+    /// bounds anyway. Two unbounded arguments may not overlap at runtime, as the later may be empty.
+    fn overlap(&self, other: &Known) -> Option<u128> {
+        let start = self.start.max(other.start);
+        let end = match (self.end, other.end) {
+            (Some(one), Some(two)) => one.min(two),
+            (Some(end), None) | (None, Some(end)) => end,
+            (None, None) => return None,
+        };
+        (start < end).then_some(start)
+    }
+}
+
+/// An argument of the macros, optionally marked `mut`.
+struct Argument {
+    exclusive: bool,
+    expr: Expr,
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let exclusive = input.parse::<Option<Token![mut]>>()?.is_some();
+        Ok(Argument {
+            exclusive,
+            expr: input.parse()?,
+        })
+    }
+}
+
+fn check(input: TokenStream2, ordered: bool) -> syn::Result<()> {
+    let arguments = Punctuated::<Argument, Token![,]>::parse_terminated.parse2(input.clone())?;
+    if arguments.len() < 2 {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected a slice followed by at least one index",
+        ));
+    }
+    let mixed = arguments.iter().any(|argument| argument.exclusive);
+    let mut known: Vec<(usize, &Argument, Known)> = Vec::new();
+    for (position, argument) in arguments.iter().skip(1).enumerate() {
+        if let Some(span) = known_span(&argument.expr)? {
+            known.push((position, argument, span));
+        }
+    }
+    for (second, (second_position, argument, second_known)) in known.iter().enumerate() {
+        for (first_position, first_argument, first_known) in &known[..second] {
+            let exclusive = !mixed || first_argument.exclusive || argument.exclusive;
+            if let Some(index) = first_known.overlap(second_known).filter(|_| exclusive) {
+                return Err(syn::Error::new_spanned(
+                    &argument.expr,
+                    format!(
+                        "index `{}` is requested at positions `{}` and `{}`",
+                        index, first_position, second_position
+                    ),
+                ));
+            }
+            if ordered && second_known.start < first_known.start {
+                return Err(syn::Error::new_spanned(
+                    &argument.expr,
+                    format!(
+                        "indices are not sorted: `{}` at position `{}` is smaller than `{}` at position `{}`",
+                        second_known.start, second_position, first_known.start, first_position
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the elements covered by `argument` if all of its bounds are literals.
+fn known_span(argument: &Expr) -> syn::Result<Option<Known>> {
+    match strip(argument) {
+        Expr::Range(ExprRange {
+            start, limits, end, ..
+        }) => {
+            let start = match start.as_deref() {
+                Some(start) => literal(start)?,
+                None => Some(0),
+            };
+            let end = match end.as_deref() {
+                Some(end) => match literal(end)? {
+                    Some(end) => Some(end),
+                    None => return Ok(None),
+                },
+                None => None,
+            };
+            let Some(start) = start else {
+                return Ok(None);
+            };
+            let end = match limits {
+                RangeLimits::HalfOpen(_) => end,
+                RangeLimits::Closed(_) => end.map(|end| end.saturating_add(1)),
+            };
+            if let Some(end) = end.filter(|end| start > *end) {
+                return Err(syn::Error::new_spanned(
+                    argument,
+                    format!("range `{}..{}` is decreasing", start, end),
+                ));
+            }
+            Ok(Some(Known { start, end }))
+        }
+        _ => Ok(literal(argument)?.map(|index| Known {
+            start: index,
+            end: Some(index.saturating_add(1)),
+        })),
+    }
+}
+
+/// Returns the value of an integer literal. Negative literals are an error.
+fn literal(expr: &Expr) -> syn::Result<Option<u128>> {
+    match strip(expr) {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse::<u128>().map(Some),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match strip(&unary.expr) {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(_), ..
+            }) => Err(syn::Error::new_spanned(
+                expr,
+                "indices must not be negative",
+            )),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Removes the parentheses and invisible groups around an expression, e.g. from `macro_rules!`
+/// arguments.
+fn strip(mut expr: &Expr) -> &Expr {
+    loop {
+        match expr {
+            Expr::Paren(paren) => expr = &paren.expr,
+            Expr::Group(group) => expr = &group.expr,
+            _ => return expr,
+        }
+    }
+}
+
+//************************************************************************//
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use quote::quote;
+
+    fn message(input: proc_macro2::TokenStream, ordered: bool) -> Option<String> {
+        check(input, ordered).err().map(|error| error.to_string())
+    }
+
+    #[test]
+    fn accepts_valid_literals_and_expressions() {
+        assert_eq!(message(quote!(slice, 3, 1, 2), false), None);
+        assert_eq!(message(quote!(slice, 0..2, 2, 3..), true), None);
+        assert_eq!(message(quote!(slice, 3.., 5..), true), None);
+        assert_eq!(message(quote!(slice, i, i, j), true), None);
+        assert_eq!(message(quote!(slice, 1, start..3, 1..=0 + 1), false), None);
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        assert_eq!(
+            message(quote!(slice, 1, i, 1), false).unwrap(),
+            "index `1` is requested at positions `0` and `2`"
+        );
+        assert_eq!(
+            message(quote!(slice, 0..=4, 4), false).unwrap(),
+            "index `4` is requested at positions `0` and `1`"
+        );
+        assert_eq!(
+            message(quote!(slice, 2.., 0, 7), false).unwrap(),
+            "index `7` is requested at positions `0` and `2`"
+        );
+    }
+
+    #[test]
+    fn rejects_only_mut_overlaps_when_mixed() {
+        assert_eq!(message(quote!(slice, mut 3, 1, 1, 0..2), false), None);
+        assert_eq!(
+            message(quote!(slice, 1, 2, mut 0..=1), false).unwrap(),
+            "index `1` is requested at positions `0` and `2`"
+        );
+        assert_eq!(
+            message(quote!(slice, mut 4, mut 4), false).unwrap(),
+            "index `4` is requested at positions `0` and `1`"
+        );
+    }
+
+    #[test]
+    fn rejects_unsorted_only_when_ordered() {
+        assert_eq!(message(quote!(slice, 3, 1), false), None);
+        assert_eq!(
+            message(quote!(slice, 3, i, 1), true).unwrap(),
+            "indices are not sorted: `1` at position `2` is smaller than `3` at position `0`"
+        );
+    }
+
+    #[test]
+    fn rejects_negative_and_decreasing() {
+        assert_eq!(
+            message(quote!(slice, 2, -1), false).unwrap(),
+            "indices must not be negative"
+        );
+        assert_eq!(
+            message(quote!(slice, (-1)..3), false).unwrap(),
+            "indices must not be negative"
+        );
+        assert_eq!(
+            message(quote!(slice, 4..2), false).unwrap(),
+            "range `4..2` is decreasing"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_indices() {
+        assert_eq!(
+            message(quote!(slice), false).unwrap(),
+            "expected a slice followed by at least one index"
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::TryArenaError;
+use alloc::vec::Vec;
+
+/// A key into an `Arena`. Keys carry the generation of their slot, so a key whose value has been
+/// removed is reported as stale instead of retrieving a value inserted later into the same slot.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArenaKey {
+    index: usize,
+    generation: u64,
+}
+
+impl ArenaKey {
+    /// The index of the key's slot.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The generation of the key's slot when the key was issued.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    /// Incremented each time the value of the slot is removed.
+    generation: u64,
+    value: Option<T>,
+}
+
+/// A generational arena. Removed slots are reused by later inserts, and several values can be
+/// retrieved mutably at once with `get_many_mut` or `get_many_mut!`.
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    /// Indices of the vacant slots.
+    free: Vec<usize>,
+}
+
+impl<T> Default for Arena<T> {
+    #[inline]
+    fn default() -> Self {
+        Arena::new()
+    }
+}
+
+impl<T> Arena<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        Arena {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+        }
+    }
+
+    /// The number of values in the arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `value` into a vacant slot, or a new one, and returns its key.
+    pub fn insert(&mut self, value: T) -> ArenaKey {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.value = Some(value);
+                ArenaKey {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                ArenaKey {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Removes and returns the value of `key`, or `None` if the key is stale or missing.
+    pub fn remove(&mut self, key: ArenaKey) -> Option<T> {
+        let slot = self.slots.get_mut(key.index)?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation += 1;
+        self.free.push(key.index);
+        Some(value)
+    }
+
+    #[inline]
+    pub fn contains(&self, key: ArenaKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value of `key`, or `None` if the key is stale or missing.
+    #[inline]
+    pub fn get(&self, key: ArenaKey) -> Option<&T> {
+        self.slots
+            .get(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+
+    /// Returns a mutable reference to the value of `key`, or `None` if the key is stale or missing.
+    #[inline]
+    pub fn get_mut(&mut self, key: ArenaKey) -> Option<&mut T> {
+        self.slots
+            .get_mut(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+
+    /// Returns mutable references to the values of the requested keys.
+    /// Panics if any key is stale, missing or duplicated.
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [ArenaKey; N]) -> [&mut T; N] {
+        match self.try_get_many_mut(keys) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references to the values of the requested keys.
+    /// Returns `TryArenaError` if any key is stale, missing or duplicated. Duplicates are reported
+    /// first, then the first stale or missing key.
+    pub fn try_get_many_mut<const N: usize>(
+        &mut self,
+        keys: [ArenaKey; N],
+    ) -> Result<[&mut T; N], TryArenaError> {
+        for second in 1..N {
+            if let Some(first) = keys[..second].iter().position(|key| *key == keys[second]) {
+                return Err(TryArenaError::DuplicateKey { first, second });
+            }
+        }
+        for (position, key) in keys.iter().enumerate() {
+            match self.slots.get(key.index) {
+                Some(slot) if slot.generation != key.generation => {
+                    return Err(TryArenaError::StaleKey {
+                        position,
+                        generation: key.generation,
+                        current: slot.generation,
+                    });
+                }
+                Some(Slot { value: Some(_), .. }) => {}
+                // A vacant slot at the key's generation has never issued it.
+                Some(_) | None => return Err(TryArenaError::MissingKey { position }),
+            }
+        }
+        // Distinct keys of occupied slots at their generation have distinct indices.
+        let ptr = self.slots.as_mut_ptr();
+        Ok(
+            keys.map(|key| match unsafe { &mut (*ptr.add(key.index)).value } {
+                Some(value) => value,
+                None => unreachable!("The slot of a validated key is occupied"),
+            }),
+        )
+    }
+}
+
+/// Returns mutable references to the values of the requested keys of an `Arena`.
+/// Panics if any key is stale, missing or duplicated.
+#[macro_export]
+macro_rules! get_many_mut {
+    ($arena:expr, $( $key:expr ),+) => {{
+        let mut iter = $crate::Arena::get_many_mut($arena, [$($key),+]).into_iter();
+        ($($crate::next_of!(iter, $key),)+)
+    }};
+}
+
+/// Returns mutable references to the values of the requested keys of an `Arena`.
+/// Returns `TryArenaError` if any key is stale, missing or duplicated.
+#[macro_export]
+macro_rules! try_get_many_mut {
+    ($arena:expr, $( $key:expr ),+) => {{
+        $crate::Arena::try_get_many_mut($arena, [$($key),+]).map(|array| {
+            let mut iter = array.into_iter();
+            ($($crate::next_of!(iter, $key),)+)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arena, TryArenaError};
+
+    #[test]
+    fn insert_remove_reuse() {
+        let mut arena = Arena::new();
+        let one = arena.insert(1);
+        let two = arena.insert(2);
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena.remove(one), Some(1));
+        assert_eq!(arena.remove(one), None);
+        let three = arena.insert(3);
+        assert_eq!(three.index(), one.index());
+        assert_eq!(three.generation(), one.generation() + 1);
+        assert_eq!(arena.get(one), None);
+        assert_eq!(arena.get(three), Some(&3));
+        assert!(arena.contains(two));
+        assert_eq!(arena.len(), 2);
+    }
+
+    #[test]
+    fn get_many_mut_macro() {
+        let mut arena = Arena::new();
+        let alice = arena.insert(100);
+        let bob = arena.insert(50);
+        let (from, to) = get_many_mut!(&mut arena, alice, bob);
+        *from -= 30;
+        *to += 30;
+        assert_eq!((arena.get(alice), arena.get(bob)), (Some(&70), Some(&80)));
+        let [bob_value] = arena.get_many_mut([bob]);
+        *bob_value = 0;
+        assert_eq!(arena.get(bob), Some(&0));
+    }
+
+    #[test]
+    fn try_get_many_mut_errors() {
+        let mut arena = Arena::new();
+        let one = arena.insert(1);
+        let two = arena.insert(2);
+        arena.remove(one);
+        let three = arena.insert(3);
+        assert_eq!(
+            arena.try_get_many_mut([two, one]),
+            Err(TryArenaError::StaleKey {
+                position: 1,
+                generation: 0,
+                current: 1
+            })
+        );
+        assert_eq!(
+            arena.try_get_many_mut([one, three, one]),
+            Err(TryArenaError::DuplicateKey {
+                first: 0,
+                second: 2
+            })
+        );
+        let mut other = Arena::new();
+        other.insert(0);
+        other.insert(0);
+        let missing = other.insert(0);
+        assert_eq!(
+            try_get_many_mut!(&mut arena, three, missing),
+            Err(TryArenaError::MissingKey { position: 1 })
+        );
+        let (three_value, two_value) = try_get_many_mut!(&mut arena, three, two).unwrap();
+        core::mem::swap(three_value, two_value);
+        assert_eq!(arena.remove(two), Some(3));
+        // A key from another arena can match the generation of a vacant slot.
+        let mut other = Arena::new();
+        let removed = other.insert(0);
+        other.remove(removed);
+        let foreign = other.insert(0);
+        assert_eq!((foreign.index(), foreign.generation()), (0, 1));
+        let mut vacant = Arena::new();
+        let removed = vacant.insert(0);
+        vacant.remove(removed);
+        assert_eq!(
+            vacant.try_get_many_mut([foreign]),
+            Err(TryArenaError::MissingKey { position: 0 })
+        );
+    }
+
+    #[should_panic(
+        expected = "Stale key. Requested key at position `0` has generation `0` while its slot has generation `1`."
+    )]
+    #[test]
+    fn get_many_mut_stale_message() {
+        let mut arena = Arena::new();
+        let key = arena.insert(1);
+        arena.remove(key);
+        arena.insert(2);
+        let _values = arena.get_many_mut([key]);
+    }
+}
@@ -0,0 +1,593 @@
+//! Validation of requested indices. The strategy is picked from the input:
+//! - Up to [`PAIRWISE_MAX_INDICES`] indices are compared pairwise.
+//! - Indices that are already strictly increasing are accepted in one pass.
+//! - With `alloc`, indices that are dense relative to the slice length are checked with a bitset.
+//! - Otherwise a copy of the indices is sorted and adjacent indices are compared.
+//!
+//! The passes over large requests are vectorised where the CPU supports it, see [`crate::simd`].
+//!
+//! Every strategy reports the same error for the same input. Duplicates are reported before out of
+//! bounds indices, a duplicate is reported as the smallest duplicated index with the positions of
+//! its first two requests, and an out of bounds index is reported as the first one requested.
+//!
+//! Requests that mix indices and ranges are resolved to [`Span`]s first. Overlapping spans are
+//! reported like duplicate indices, with the smallest index they share.
+
+use crate::{simd, Span, TryIndicesError, TryIndicesOrderedError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Requests with at most this many indices are compared pairwise.
+const PAIRWISE_MAX_INDICES: usize = 16;
+
+/// The bitset is used when the slice length is at most this many times the number of indices,
+/// i.e. when clearing the bitset costs no more than a few words per index.
+#[cfg(feature = "alloc")]
+const BITSET_MAX_BITS_PER_INDEX: usize = 256;
+
+/// Checks that `indices` contains no duplicates and that every index is in bounds for a slice of
+/// length `len`. `check` must be a copy of `indices` and may be sorted in place.
+pub(crate) fn check_indices(
+    indices: &[usize],
+    check: &mut [usize],
+    len: usize,
+) -> Result<(), TryIndicesError> {
+    debug_assert_eq!(indices, check);
+    if let Some(result) = check_indices_without_scratch(indices, len) {
+        return result;
+    }
+    check_indices_by_sort(indices, check, len)
+}
+
+/// Same as [`check_indices`], but allocates its own scratch space when needed and may use a
+/// bitset.
+#[cfg(feature = "alloc")]
+pub(crate) fn check_indices_alloc(indices: &[usize], len: usize) -> Result<(), TryIndicesError> {
+    if let Some(result) = check_indices_without_scratch(indices, len) {
+        return result;
+    }
+    if len <= indices.len().saturating_mul(BITSET_MAX_BITS_PER_INDEX)
+        && check_indices_by_bitset(indices, len)
+    {
+        return Ok(());
+    }
+    let mut check: Vec<usize> = indices.to_vec();
+    check_indices_by_sort(indices, &mut check, len)
+}
+
+/// Checks that `indices` is sorted smallest to largest, contains no duplicates and that every
+/// index is in bounds for a slice of length `len`.
+pub(crate) fn check_indices_ordered(
+    indices: &[usize],
+    len: usize,
+) -> Result<(), TryIndicesOrderedError> {
+    if indices.is_empty() {
+        return Ok(());
+    }
+    let indices_len_minus_one = indices.len() - 1;
+    for i in 0..indices_len_minus_one {
+        if indices[i] >= len {
+            return Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: indices[i],
+                position: i,
+                len,
+            });
+        }
+        if indices[i] == indices[i + 1] {
+            return Err(TryIndicesOrderedError::DuplicateIndex {
+                index: indices[i],
+                first: i,
+                second: i + 1,
+            });
+        }
+        if indices[i] > indices[i + 1] {
+            return Err(TryIndicesOrderedError::NotSorted {
+                index: indices[i + 1],
+                position: i + 1,
+                previous: indices[i],
+            });
+        }
+    }
+    if indices[indices_len_minus_one] >= len {
+        return Err(TryIndicesOrderedError::IndexOutOfBounds {
+            index: indices[indices_len_minus_one],
+            position: indices_len_minus_one,
+            len,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that no two `spans` overlap and that every span is in bounds for a slice of length
+/// `len`. Requests of single indices only are checked like [`check_indices`].
+#[doc(hidden)]
+pub fn check_requests<const N: usize>(
+    spans: &[Span; N],
+    len: usize,
+) -> Result<(), TryIndicesError> {
+    if spans.iter().all(|span| matches!(span, Span::Index(_))) {
+        let indices = spans.map(Span::start);
+        let mut check = indices;
+        return check_indices(&indices, &mut check, len);
+    }
+    check_spans(spans, |_, _| true, len)
+}
+
+/// Same as [`check_requests`], but only spans where at least one of the two is `exclusive` may not
+/// overlap.
+#[doc(hidden)]
+pub fn check_requests_mixed<const N: usize>(
+    spans: &[Span; N],
+    exclusive: &[bool; N],
+    len: usize,
+) -> Result<(), TryIndicesError> {
+    if exclusive.iter().all(|exclusive| *exclusive) {
+        return check_requests(spans, len);
+    }
+    check_spans(
+        spans,
+        |first, second| exclusive[first] || exclusive[second],
+        len,
+    )
+}
+
+/// Checks that no two `spans` for which `exclusive` holds overlap and that every span is in
+/// bounds. The smallest shared index is reported with the first pair of positions requesting it.
+fn check_spans<const N: usize>(
+    spans: &[Span; N],
+    exclusive: impl Fn(usize, usize) -> bool,
+    len: usize,
+) -> Result<(), TryIndicesError> {
+    let mut smallest: Option<usize> = None;
+    for second in 1..N {
+        for first in 0..second {
+            if !exclusive(first, second) {
+                continue;
+            }
+            if let Some(index) = spans[first].overlap(spans[second]) {
+                smallest = Some(smallest.map_or(index, |smallest| smallest.min(index)));
+            }
+        }
+    }
+    if let Some(index) = smallest {
+        for second in 1..N {
+            if !spans[second].contains(index) {
+                continue;
+            }
+            if let Some(first) =
+                (0..second).find(|first| spans[*first].contains(index) && exclusive(*first, second))
+            {
+                return Err(TryIndicesError::DuplicateIndex {
+                    index,
+                    first,
+                    second,
+                });
+            }
+        }
+    }
+    for (position, span) in spans.iter().enumerate() {
+        match *span {
+            Span::Index(index) if index >= len => {
+                return Err(TryIndicesError::IndexOutOfBounds {
+                    index,
+                    position,
+                    len,
+                });
+            }
+            Span::ToMax { .. } => {
+                return Err(TryIndicesError::IndexOutOfBounds {
+                    index: usize::MAX,
+                    position,
+                    len,
+                });
+            }
+            Span::Range { start, end } if start > end || end > len => {
+                return Err(TryIndicesError::InvalidRange {
+                    start,
+                    end,
+                    position,
+                    len,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `spans` are sorted by their start, that no two spans overlap and that every span is
+/// in bounds for a slice of length `len`. Positions are checked in order, like
+/// [`check_indices_ordered`].
+#[doc(hidden)]
+pub fn check_requests_ordered<const N: usize>(
+    spans: &[Span; N],
+    len: usize,
+) -> Result<(), TryIndicesOrderedError> {
+    // Since the spans are sorted by start, only the span reaching furthest can overlap the next.
+    let mut furthest: Option<usize> = None;
+    for (position, span) in spans.iter().copied().enumerate() {
+        if position > 0 {
+            let previous = spans[position - 1].start();
+            if span.start() < previous {
+                return Err(TryIndicesOrderedError::NotSorted {
+                    index: span.start(),
+                    position,
+                    previous,
+                });
+            }
+        }
+        if let Some(first) = furthest {
+            if let Some(index) = spans[first].overlap(span) {
+                return Err(TryIndicesOrderedError::DuplicateIndex {
+                    index,
+                    first,
+                    second: position,
+                });
+            }
+        }
+        match span {
+            Span::Index(index) if index >= len => {
+                return Err(TryIndicesOrderedError::IndexOutOfBounds {
+                    index,
+                    position,
+                    len,
+                });
+            }
+            Span::ToMax { .. } => {
+                return Err(TryIndicesOrderedError::IndexOutOfBounds {
+                    index: usize::MAX,
+                    position,
+                    len,
+                });
+            }
+            Span::Range { start, end } if start > end || end > len => {
+                return Err(TryIndicesOrderedError::InvalidRange {
+                    start,
+                    end,
+                    position,
+                    len,
+                });
+            }
+            _ => {}
+        }
+        if !span.is_empty() && furthest.is_none_or(|furthest| span.end() > spans[furthest].end()) {
+            furthest = Some(position);
+        }
+    }
+    Ok(())
+}
+
+/// Builds the error for the smallest index in `indices` that is requested more than once.
+/// Only called once a duplicate is known to exist.
+#[cold]
+#[inline(never)]
+pub(crate) fn duplicate_index_error(indices: &[usize]) -> TryIndicesError {
+    let mut smallest: Option<usize> = None;
+    for second in 1..indices.len() {
+        let index = indices[second];
+        if smallest.is_some_and(|smallest| smallest <= index) {
+            continue;
+        }
+        if indices[..second].contains(&index) {
+            smallest = Some(index);
+        }
+    }
+    match smallest {
+        Some(index) => duplicate_error_for(indices, index),
+        None => unreachable!("No duplicate index in `{:?}`.", indices),
+    }
+}
+
+/// Builds the error for the first index in `indices` that is out of bounds for a slice of length `len`.
+/// Only called once an out of bounds index is known to exist.
+#[cold]
+#[inline(never)]
+pub(crate) fn index_out_of_bounds_error(indices: &[usize], len: usize) -> TryIndicesError {
+    match indices.iter().position(|index| *index >= len) {
+        Some(position) => TryIndicesError::IndexOutOfBounds {
+            index: indices[position],
+            position,
+            len,
+        },
+        None => unreachable!(
+            "No index in `{:?}` is out of bounds for length `{}`.",
+            indices, len
+        ),
+    }
+}
+
+//************************************************************************//
+
+/// Checks small or already sorted requests, which need no scratch space. Returns `None` if neither
+/// applies.
+#[inline]
+fn check_indices_without_scratch(
+    indices: &[usize],
+    len: usize,
+) -> Option<Result<(), TryIndicesError>> {
+    if indices.len() <= PAIRWISE_MAX_INDICES {
+        return Some(check_indices_pairwise(indices, len));
+    }
+    if simd::is_strictly_increasing(indices) {
+        if indices[indices.len() - 1] >= len {
+            return Some(Err(index_out_of_bounds_error(indices, len)));
+        }
+        return Some(Ok(()));
+    }
+    None
+}
+
+#[inline]
+fn check_indices_pairwise(indices: &[usize], len: usize) -> Result<(), TryIndicesError> {
+    for second in 1..indices.len() {
+        if indices[..second].contains(&indices[second]) {
+            return Err(duplicate_index_error(indices));
+        }
+    }
+    if indices.iter().any(|index| *index >= len) {
+        return Err(index_out_of_bounds_error(indices, len));
+    }
+    Ok(())
+}
+
+/// Returns whether `indices` are unique and in bounds. Does not build an error since the smallest
+/// duplicate is not known after a single pass; callers fall back to sorting instead.
+#[cfg(feature = "alloc")]
+fn check_indices_by_bitset(indices: &[usize], len: usize) -> bool {
+    if simd::max_index(indices) >= len {
+        return false;
+    }
+    let mut bits: Vec<u64> = alloc::vec![0; len.div_ceil(64)];
+    for index in indices {
+        let word = &mut bits[index / 64];
+        let bit = 1 << (index % 64);
+        if *word & bit != 0 {
+            return false;
+        }
+        *word |= bit;
+    }
+    true
+}
+
+fn check_indices_by_sort(
+    indices: &[usize],
+    check: &mut [usize],
+    len: usize,
+) -> Result<(), TryIndicesError> {
+    check.sort_unstable();
+    if let Some(index) = simd::find_adjacent_equal(check) {
+        return Err(duplicate_error_for(indices, index));
+    }
+    if check[check.len() - 1] >= len {
+        return Err(index_out_of_bounds_error(indices, len));
+    }
+    Ok(())
+}
+
+#[cold]
+fn duplicate_error_for(indices: &[usize], index: usize) -> TryIndicesError {
+    let mut positions = indices
+        .iter()
+        .enumerate()
+        .filter(|(_, requested)| **requested == index)
+        .map(|(position, _)| position);
+    match (positions.next(), positions.next()) {
+        (Some(first), Some(second)) => TryIndicesError::DuplicateIndex {
+            index,
+            first,
+            second,
+        },
+        _ => unreachable!("Index `{}` is not duplicated in `{:?}`.", index, indices),
+    }
+}
+
+//************************************************************************//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(indices: &[usize], len: usize) -> Result<(), TryIndicesError> {
+        let mut scratch = indices.to_vec();
+        let result = check_indices(indices, &mut scratch, len);
+        #[cfg(feature = "alloc")]
+        assert_eq!(result, check_indices_alloc(indices, len));
+        result
+    }
+
+    fn shuffled(len: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..len).collect();
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for i in (1..len).rev() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            indices.swap(i, (state % (i as u64 + 1)) as usize);
+        }
+        indices
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(check(&[], 0), Ok(()));
+        assert_eq!(check(&[], 5), Ok(()));
+    }
+
+    #[test]
+    fn pairwise() {
+        assert_eq!(check(&[3, 0, 2], 4), Ok(()));
+        assert_eq!(
+            check(&[3, 2, 3, 2], 4),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 2,
+                first: 1,
+                second: 3
+            })
+        );
+        assert_eq!(
+            check(&[3, 9, 4], 4),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 9,
+                position: 1,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_reported_before_out_of_bounds() {
+        let mut indices = shuffled(1000);
+        indices[10] = 5000;
+        indices[20] = indices[30];
+        let index = indices[30];
+        assert_eq!(
+            check(&indices, 1000),
+            Err(TryIndicesError::DuplicateIndex {
+                index,
+                first: 20,
+                second: 30
+            })
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let indices: Vec<usize> = (0..1000).map(|i| i * 3).collect();
+        assert_eq!(check(&indices, 2998), Ok(()));
+        assert_eq!(
+            check(&indices, 2997),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 2997,
+                position: 999,
+                len: 2997
+            })
+        );
+    }
+
+    #[test]
+    fn dense() {
+        let indices = shuffled(1000);
+        assert_eq!(check(&indices, 1000), Ok(()));
+        assert_eq!(
+            check(&indices, 999),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 999,
+                position: indices.iter().position(|i| *i == 999).unwrap(),
+                len: 999
+            })
+        );
+    }
+
+    #[test]
+    fn dense_duplicate() {
+        let mut indices = shuffled(1000);
+        indices[700] = indices[100];
+        indices[900] = indices[800];
+        let (smallest, first, second) = if indices[100] < indices[800] {
+            (indices[100], 100, 700)
+        } else {
+            (indices[800], 800, 900)
+        };
+        assert_eq!(
+            check(&indices, 1000),
+            Err(TryIndicesError::DuplicateIndex {
+                index: smallest,
+                first,
+                second
+            })
+        );
+    }
+
+    #[test]
+    fn sparse() {
+        let indices: Vec<usize> = shuffled(1000).into_iter().map(|i| i * 1000).collect();
+        assert_eq!(check(&indices, 1_000_000), Ok(()));
+        let mut duplicated = indices.clone();
+        duplicated[999] = duplicated[0];
+        assert_eq!(
+            check(&duplicated, 1_000_000),
+            Err(TryIndicesError::DuplicateIndex {
+                index: indices[0],
+                first: 0,
+                second: 999
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_index_error_reports_smallest() {
+        assert_eq!(
+            duplicate_index_error(&[4, 1, 4, 1]),
+            TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 1,
+                second: 3
+            }
+        );
+    }
+
+    #[test]
+    fn requests_report_smallest_overlap() {
+        let spans = [
+            Span::Range { start: 6, end: 9 },
+            Span::Index(8),
+            Span::Range { start: 2, end: 5 },
+            Span::Index(20),
+            Span::Range { start: 4, end: 7 },
+        ];
+        assert_eq!(
+            check_requests(&spans, 10),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 4,
+                first: 2,
+                second: 4
+            })
+        );
+    }
+
+    #[test]
+    fn requests_decreasing_range_does_not_overlap() {
+        let spans = [Span::Range { start: 4, end: 1 }, Span::Index(2)];
+        assert_eq!(
+            check_requests(&spans, 5),
+            Err(TryIndicesError::InvalidRange {
+                start: 4,
+                end: 1,
+                position: 0,
+                len: 5
+            })
+        );
+    }
+
+    #[test]
+    fn requests_of_indices_match_check_indices() {
+        let spans = [Span::Index(3), Span::Index(1), Span::Index(3)];
+        assert_eq!(check_requests(&spans, 2), check(&[3, 1, 3], 2));
+    }
+
+    #[test]
+    fn mixed_requests_only_report_exclusive_overlaps() {
+        let spans = [
+            Span::Range { start: 0, end: 4 },
+            Span::Index(1),
+            Span::Index(3),
+            Span::Range { start: 3, end: 5 },
+        ];
+        assert_eq!(
+            check_requests_mixed(&spans, &[false, false, true, false], 5),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 2
+            })
+        );
+        assert_eq!(
+            check_requests_mixed(&spans, &[false, false, false, false], 5),
+            Ok(())
+        );
+        assert_eq!(
+            check_requests_mixed(&spans, &[true; 4], 5),
+            check_requests(&spans, 5)
+        );
+    }
+}
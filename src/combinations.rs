@@ -0,0 +1,114 @@
+/// Calls `f` with mutable references to every combination of `K` distinct elements of `slice`, in
+/// lexicographic order of their indices, e.g. `[0, 1, 2]`, `[0, 1, 3]`, ..., `[n - 3, n - 2, n - 1]`
+/// for `K = 3`. Elements are in the order of their indices. Combinations are generated distinct, so
+/// no bounds or duplicate checks are performed per call.
+///
+/// `K` can be given as `for_each_combination_mut::<3, _>(slice, |[a, b, c]| ...)`.
+pub fn for_each_combination_mut<const K: usize, T>(
+    slice: &mut [T],
+    mut f: impl FnMut([&mut T; K]),
+) {
+    let len = slice.len();
+    if K > len {
+        return;
+    }
+    let ptr = slice.as_mut_ptr();
+    let mut indices: [usize; K] = core::array::from_fn(|i| i);
+    loop {
+        // `indices` is strictly increasing and its last index is below `len`.
+        f(core::array::from_fn(|i| unsafe {
+            &mut *ptr.add(indices[i])
+        }));
+        let Some(i) = (0..K).rev().find(|i| indices[*i] < len - K + *i) else {
+            return;
+        };
+        indices[i] += 1;
+        for j in i + 1..K {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+/// Calls `f` with mutable references to every pair of distinct elements of `slice`, in the order of
+/// `for_each_combination_mut::<2, _>`.
+pub fn pairs_mut<T, F>(slice: &mut [T], mut f: F)
+where
+    F: FnMut(&mut T, &mut T),
+{
+    for i in 1..slice.len() {
+        let (head, tail) = slice.split_at_mut(i);
+        let first = &mut head[i - 1];
+        for second in tail {
+            f(first, second);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{for_each_combination_mut, pairs_mut};
+
+    #[test]
+    fn combinations_of_three() {
+        let mut data = [0u32, 1, 2, 3, 4];
+        let mut visited = [[0; 3]; 10];
+        let mut count = 0;
+        for_each_combination_mut::<3, _>(&mut data, |[a, b, c]| {
+            visited[count] = [*a, *b, *c];
+            count += 1;
+            *a += 10;
+        });
+        assert_eq!(count, 10);
+        assert_eq!(visited[0], [0, 1, 2]);
+        assert_eq!(visited[1], [10, 1, 3]);
+        assert_eq!(visited[9], [2, 3, 4]);
+        // Each element is first in C(n - 1 - index, 2) combinations.
+        assert_eq!(data, [60, 31, 12, 3, 4]);
+    }
+
+    #[test]
+    fn combinations_edge_cases() {
+        let mut data = [1, 2];
+        let mut count = 0;
+        for_each_combination_mut(&mut data, |_: [&mut i32; 3]| count += 1);
+        assert_eq!(count, 0);
+        for_each_combination_mut(&mut data, |_: [&mut i32; 0]| count += 1);
+        assert_eq!(count, 1);
+        for_each_combination_mut(&mut data, |[a, b]: [&mut i32; 2]| core::mem::swap(a, b));
+        assert_eq!(data, [2, 1]);
+        let mut empty: [i32; 0] = [];
+        for_each_combination_mut(&mut empty, |_: [&mut i32; 1]| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn pairs_mut_conserves_momentum() {
+        let mut velocities = [1i64, -3, 4, 0];
+        let mut count = 0;
+        pairs_mut(&mut velocities, |a, b| {
+            let exchange = (*a - *b) / 2;
+            *a -= exchange;
+            *b += exchange;
+            count += 1;
+        });
+        assert_eq!(count, 6);
+        assert_eq!(velocities.iter().sum::<i64>(), 2);
+    }
+
+    #[test]
+    fn pairs_mut_matches_combinations() {
+        let mut data = [0usize, 1, 2, 3, 4, 5];
+        let mut pairs = [(0, 0); 15];
+        let mut count = 0;
+        pairs_mut(&mut data, |a, b| {
+            pairs[count] = (*a, *b);
+            count += 1;
+        });
+        count = 0;
+        for_each_combination_mut(&mut data, |[a, b]: [&mut usize; 2]| {
+            assert_eq!(pairs[count], (*a, *b));
+            count += 1;
+        });
+        assert_eq!(count, 15);
+    }
+}
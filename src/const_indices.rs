@@ -0,0 +1,113 @@
+use crate::claim_array;
+
+/// A set of `K` indices known at compile time, e.g.
+/// ```
+/// struct Corners;
+///
+/// impl indices::ConstIndices<4> for Corners {
+///     const INDICES: [usize; 4] = [0, 3, 12, 15];
+/// }
+///
+/// let mut grid = [0; 16];
+/// let [a, b, c, d] = indices::get_const_mut(&mut grid, Corners);
+/// ```
+/// `indices_const!` implements it for the indices passed to it.
+pub trait ConstIndices<const K: usize> {
+    const INDICES: [usize; K];
+}
+
+/// Returns mutable references for the indices of `C` in the provided array. Fails to compile if any
+/// index is out of bounds for the array or duplicated, and performs no runtime checks.
+///
+/// ```compile_fail
+/// struct Indices;
+///
+/// impl indices::ConstIndices<2> for Indices {
+///     const INDICES: [usize; 2] = [1, 4];
+/// }
+///
+/// let mut data = [0; 4];
+/// let [one, four] = indices::get_const_mut(&mut data, Indices);
+/// ```
+#[inline]
+pub fn get_const_mut<C: ConstIndices<K>, T, const K: usize, const N: usize>(
+    array: &mut [T; N],
+    _indices: C,
+) -> [&mut T; K] {
+    const { check_const_indices(&C::INDICES, N) };
+    unsafe { claim_array(array, &C::INDICES) }
+}
+
+/// Panics, which fails compilation in a `const` block, if any index is out of bounds for an array
+/// of length `len` or duplicated.
+const fn check_const_indices<const K: usize>(indices: &[usize; K], len: usize) {
+    let mut second = 0;
+    while second < K {
+        assert!(
+            indices[second] < len,
+            "An index is out of bounds for the array."
+        );
+        let mut first = 0;
+        while first < second {
+            assert!(
+                indices[first] != indices[second],
+                "An index is requested more than once."
+            );
+            first += 1;
+        }
+        second += 1;
+    }
+}
+
+/// Returns mutable references for the requested constant indices of an array, e.g.
+/// `indices_const!(&mut array, 1, 3, 7)`. Fails to compile if any index is out of bounds for the
+/// array or duplicated, and performs no runtime checks.
+///
+/// ```compile_fail
+/// let mut data = [0; 4];
+/// let (one, two, one_again) = indices::indices_const!(&mut data, 1, 2, 1);
+/// ```
+#[macro_export]
+macro_rules! indices_const {
+    ($array:expr, $( $index:expr ),+ $(,)?) => {{
+        struct Indices;
+
+        impl $crate::ConstIndices<{ [$(stringify!($index)),+].len() }> for Indices {
+            const INDICES: [usize; [$(stringify!($index)),+].len()] = [$($index),+];
+        }
+
+        let mut iter = $crate::get_const_mut($array, Indices).into_iter();
+        ($($crate::next_of!(iter, $index),)+)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_const_mut, ConstIndices};
+
+    struct Neighbours;
+
+    impl ConstIndices<3> for Neighbours {
+        const INDICES: [usize; 3] = [4, 1, 7];
+    }
+
+    #[test]
+    fn const_indices_trait() {
+        let mut grid = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let [centre, above, below] = get_const_mut(&mut grid, Neighbours);
+        *centre += *above + *below;
+        core::mem::swap(above, below);
+        assert_eq!(grid, [0, 7, 2, 3, 12, 5, 6, 1, 8]);
+    }
+
+    #[test]
+    fn indices_const_macro() {
+        const LAST: usize = 4;
+        let mut data = [5, 4, 3, 2, 1];
+        let (zero, last) = indices_const!(&mut data, 0, LAST);
+        core::mem::swap(zero, last);
+        let (two,) = indices_const!(&mut data, 1 + 1,);
+        *two = 0;
+        assert_eq!(data, [1, 4, 0, 2, 5]);
+    }
+}
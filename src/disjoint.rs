@@ -0,0 +1,223 @@
+use crate::check::{check_indices, index_out_of_bounds_error};
+#[cfg(feature = "alloc")]
+use crate::claim_vec;
+use crate::{claim_array, TryIndicesError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A set of `N` distinct indices that is validated once and can then be applied to any slice that
+/// is long enough, at the cost of a single length comparison.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DisjointIndices<const N: usize> {
+    indices: [usize; N],
+    /// One past the largest index.
+    min_len: usize,
+}
+
+impl<const N: usize> DisjointIndices<N> {
+    /// Returns `TryIndicesError` if any index is duplicated.
+    pub fn new(indices: [usize; N]) -> Result<Self, TryIndicesError> {
+        Self::with_max_len(indices, usize::MAX)
+    }
+
+    /// Returns `TryIndicesError` if any index is duplicated, or out of bounds for a slice of length
+    /// `max_len`.
+    pub fn with_max_len(indices: [usize; N], max_len: usize) -> Result<Self, TryIndicesError> {
+        let mut check = indices;
+        check_indices(&indices, &mut check, max_len)?;
+        Ok(DisjointIndices {
+            indices,
+            min_len: min_len(&indices, max_len)?,
+        })
+    }
+
+    #[inline]
+    pub fn indices(&self) -> &[usize; N] {
+        &self.indices
+    }
+
+    /// The length of the shortest slice the indices can be applied to.
+    #[inline]
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Returns mutable references for the indices in the provided slice.
+    /// Panics if the slice is shorter than `min_len`.
+    #[inline]
+    pub fn apply<'a, T>(&self, slice: &'a mut [T]) -> [&'a mut T; N] {
+        match self.try_apply(slice) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references for the indices in the provided slice.
+    /// Returns `TryIndicesError::IndexOutOfBounds` for the first index that is out of bounds if the
+    /// slice is shorter than `min_len`.
+    #[inline]
+    pub fn try_apply<'a, T>(&self, slice: &'a mut [T]) -> Result<[&'a mut T; N], TryIndicesError> {
+        if slice.len() < self.min_len {
+            return Err(index_out_of_bounds_error(&self.indices, slice.len()));
+        }
+        Ok(unsafe { claim_array(slice, &self.indices) })
+    }
+}
+
+/// A set of distinct indices, only known at runtime, that is validated once and can then be
+/// applied to any slice that is long enough, at the cost of a single length comparison.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DisjointIndicesVec {
+    indices: Vec<usize>,
+    /// One past the largest index.
+    min_len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl DisjointIndicesVec {
+    /// Returns `TryIndicesError` if any index is duplicated.
+    pub fn new(indices: Vec<usize>) -> Result<Self, TryIndicesError> {
+        Self::with_max_len(indices, usize::MAX)
+    }
+
+    /// Returns `TryIndicesError` if any index is duplicated, or out of bounds for a slice of length
+    /// `max_len`.
+    pub fn with_max_len(indices: Vec<usize>, max_len: usize) -> Result<Self, TryIndicesError> {
+        crate::check::check_indices_alloc(&indices, max_len)?;
+        let min_len = min_len(&indices, max_len)?;
+        Ok(DisjointIndicesVec { indices, min_len })
+    }
+
+    #[inline]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The length of the shortest slice the indices can be applied to.
+    #[inline]
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Returns mutable references for the indices in the provided slice.
+    /// Panics if the slice is shorter than `min_len`.
+    #[inline]
+    pub fn apply<'a, T>(&self, slice: &'a mut [T]) -> Vec<&'a mut T> {
+        match self.try_apply(slice) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references for the indices in the provided slice.
+    /// Returns `TryIndicesError::IndexOutOfBounds` for the first index that is out of bounds if the
+    /// slice is shorter than `min_len`.
+    pub fn try_apply<'a, T>(&self, slice: &'a mut [T]) -> Result<Vec<&'a mut T>, TryIndicesError> {
+        if slice.len() < self.min_len {
+            return Err(index_out_of_bounds_error(&self.indices, slice.len()));
+        }
+        Ok(unsafe { claim_vec(slice, &self.indices) })
+    }
+}
+
+/// One past the largest index, or an error if that does not fit in a `usize`.
+#[inline]
+fn min_len(indices: &[usize], max_len: usize) -> Result<usize, TryIndicesError> {
+    match indices.iter().max() {
+        Some(max) => max
+            .checked_add(1)
+            .ok_or_else(|| index_out_of_bounds_error(indices, max_len)),
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use crate::DisjointIndicesVec;
+    use crate::{DisjointIndices, TryIndicesError};
+
+    #[test]
+    fn disjoint_indices_apply_to_many_slices() {
+        let indices = DisjointIndices::new([3, 0, 1]).unwrap();
+        assert_eq!(indices.min_len(), 4);
+        let mut first = [0, 1, 2, 3];
+        let mut second = [4, 5, 6, 7, 8];
+        for row in [&mut first[..], &mut second[..]] {
+            let [three, zero, one] = indices.apply(row);
+            *three += *zero + *one;
+        }
+        assert_eq!(first, [0, 1, 2, 4]);
+        assert_eq!(second, [4, 5, 6, 16, 8]);
+    }
+
+    #[test]
+    fn disjoint_indices_errors() {
+        assert_eq!(
+            DisjointIndices::new([2, 5, 2]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 2,
+                first: 0,
+                second: 2
+            })
+        );
+        assert_eq!(
+            DisjointIndices::with_max_len([2, 5], 5),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+        let indices = DisjointIndices::with_max_len([4, 1, 3], 5).unwrap();
+        let mut data = [0; 3];
+        assert_eq!(
+            indices.try_apply(&mut data),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 4,
+                position: 0,
+                len: 3
+            })
+        );
+        let empty = DisjointIndices::new([]).unwrap();
+        assert_eq!(empty.try_apply(&mut data), Ok([]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn disjoint_indices_vec() {
+        let indices = DisjointIndicesVec::new((0..40).rev().step_by(2).collect()).unwrap();
+        assert_eq!(indices.min_len(), 40);
+        let mut data: Vec<usize> = (1..=50).collect();
+        for element in indices.apply(&mut data) {
+            *element = 0;
+        }
+        assert_eq!(data.iter().filter(|element| **element == 0).count(), 20);
+        assert_eq!(
+            DisjointIndicesVec::new(vec![1, 7, 1]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 2
+            })
+        );
+        assert_eq!(
+            indices.try_apply(&mut data[..39]),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 39,
+                position: 0,
+                len: 39
+            })
+        );
+    }
+
+    #[should_panic(
+        expected = "Index out of bounds. Requested index was `3` at position `1` while slice length was `2`."
+    )]
+    #[test]
+    fn disjoint_indices_apply_message() {
+        let indices = DisjointIndices::new([0, 3]).unwrap();
+        let _elements = indices.apply(&mut [1, 2]);
+    }
+}
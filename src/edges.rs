@@ -0,0 +1,178 @@
+use crate::TryEdgesError;
+
+/// How `for_each_pair_mut` handles an edge whose endpoints are the same index. To call a closure with
+/// the single element instead, use `for_each_pair_or_self_mut`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SelfLoops {
+    /// The edge is skipped.
+    Skip,
+    /// The edge is reported as `TryEdgesError::SelfLoop`.
+    Error,
+}
+
+/// Calls `f` with mutable references to both endpoints of every edge, in order.
+/// Every edge is checked once up front, so `f` is not called at all for an invalid request.
+/// Panics if any endpoint is out of bounds, or if an edge is a self-loop with `SelfLoops::Error`.
+pub fn for_each_pair_mut<T>(
+    slice: &mut [T],
+    edges: &[(usize, usize)],
+    self_loops: SelfLoops,
+    f: impl FnMut(&mut T, &mut T),
+) {
+    if let Err(error) = try_for_each_pair_mut(slice, edges, self_loops, f) {
+        panic!("{}", error)
+    }
+}
+
+/// Calls `f` with mutable references to both endpoints of every edge, in order.
+/// Every edge is checked once up front, so `f` is not called at all for an invalid request.
+/// Returns `TryEdgesError` if any endpoint is out of bounds, or if an edge is a self-loop with
+/// `SelfLoops::Error`.
+pub fn try_for_each_pair_mut<T>(
+    slice: &mut [T],
+    edges: &[(usize, usize)],
+    self_loops: SelfLoops,
+    mut f: impl FnMut(&mut T, &mut T),
+) -> Result<(), TryEdgesError> {
+    check_edges(edges, slice.len(), self_loops == SelfLoops::Error)?;
+    let ptr = slice.as_mut_ptr();
+    for &(one, two) in edges {
+        if one != two {
+            unsafe { f(&mut *ptr.add(one), &mut *ptr.add(two)) }
+        }
+    }
+    Ok(())
+}
+
+/// Calls `f` with mutable references to both endpoints of every edge, and `g` with a mutable
+/// reference to the single endpoint of every self-loop, in order.
+/// Every edge is checked once up front, so neither closure is called for an invalid request.
+/// Panics if any endpoint is out of bounds.
+pub fn for_each_pair_or_self_mut<T>(
+    slice: &mut [T],
+    edges: &[(usize, usize)],
+    f: impl FnMut(&mut T, &mut T),
+    g: impl FnMut(&mut T),
+) {
+    if let Err(error) = try_for_each_pair_or_self_mut(slice, edges, f, g) {
+        panic!("{}", error)
+    }
+}
+
+/// Calls `f` with mutable references to both endpoints of every edge, and `g` with a mutable
+/// reference to the single endpoint of every self-loop, in order.
+/// Every edge is checked once up front, so neither closure is called for an invalid request.
+/// Returns `TryEdgesError` if any endpoint is out of bounds.
+pub fn try_for_each_pair_or_self_mut<T>(
+    slice: &mut [T],
+    edges: &[(usize, usize)],
+    mut f: impl FnMut(&mut T, &mut T),
+    mut g: impl FnMut(&mut T),
+) -> Result<(), TryEdgesError> {
+    check_edges(edges, slice.len(), false)?;
+    let ptr = slice.as_mut_ptr();
+    for &(one, two) in edges {
+        if one != two {
+            unsafe { f(&mut *ptr.add(one), &mut *ptr.add(two)) }
+        } else {
+            unsafe { g(&mut *ptr.add(one)) }
+        }
+    }
+    Ok(())
+}
+
+/// Checks edges in order. Within an edge, a self-loop is reported before an out of bounds endpoint,
+/// like a duplicate index.
+fn check_edges(
+    edges: &[(usize, usize)],
+    len: usize,
+    reject_self_loops: bool,
+) -> Result<(), TryEdgesError> {
+    for (edge, &(one, two)) in edges.iter().enumerate() {
+        if reject_self_loops && one == two {
+            return Err(TryEdgesError::SelfLoop { index: one, edge });
+        }
+        if let Some(index) = [one, two].into_iter().find(|index| *index >= len) {
+            return Err(TryEdgesError::IndexOutOfBounds { index, edge, len });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        for_each_pair_mut, for_each_pair_or_self_mut, try_for_each_pair_mut,
+        try_for_each_pair_or_self_mut, SelfLoops, TryEdgesError,
+    };
+
+    #[test]
+    fn for_each_pair_mut_relaxes_edges() {
+        let mut distances = [0, 100, 100, 100];
+        let edges = [(0, 1), (1, 2), (0, 2), (2, 3), (3, 3)];
+        for_each_pair_mut(&mut distances, &edges, SelfLoops::Skip, |from, to| {
+            *to = (*to).min(*from + 1);
+        });
+        assert_eq!(distances, [0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn try_for_each_pair_mut_checks_up_front() {
+        let mut data = [1, 2, 3];
+        let mut calls = 0;
+        assert_eq!(
+            try_for_each_pair_mut(&mut data, &[(0, 1), (2, 2)], SelfLoops::Error, |_, _| {
+                calls += 1
+            }),
+            Err(TryEdgesError::SelfLoop { index: 2, edge: 1 })
+        );
+        assert_eq!(
+            try_for_each_pair_mut(&mut data, &[(0, 1), (1, 3)], SelfLoops::Skip, |_, _| {
+                calls += 1
+            }),
+            Err(TryEdgesError::IndexOutOfBounds {
+                index: 3,
+                edge: 1,
+                len: 3
+            })
+        );
+        assert_eq!(calls, 0);
+        assert_eq!(
+            try_for_each_pair_mut(&mut data, &[(5, 5)], SelfLoops::Error, |_, _| {}),
+            Err(TryEdgesError::SelfLoop { index: 5, edge: 0 })
+        );
+    }
+
+    #[should_panic(
+        expected = "Self-loops are not allowed. Index `1` was requested twice in the edge at position `0`."
+    )]
+    #[test]
+    fn for_each_pair_mut_self_loop_message() {
+        let mut data = [1, 2, 3];
+        for_each_pair_mut(&mut data, &[(1, 1)], SelfLoops::Error, |_, _| {});
+    }
+
+    #[test]
+    fn for_each_pair_or_self_mut_springs() {
+        let mut forces = [0, 0, 0];
+        let edges = [(0, 1), (1, 1), (1, 2), (2, 0)];
+        for_each_pair_or_self_mut(
+            &mut forces,
+            &edges,
+            |one, two| {
+                *one += 1;
+                *two -= 1;
+            },
+            |own| *own += 10,
+        );
+        assert_eq!(forces, [0, 10, 0]);
+        assert_eq!(
+            try_for_each_pair_or_self_mut(&mut forces, &[(4, 4)], |_, _| {}, |_| {}),
+            Err(TryEdgesError::IndexOutOfBounds {
+                index: 4,
+                edge: 0,
+                len: 3
+            })
+        );
+    }
+}
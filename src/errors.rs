@@ -1,43 +1,477 @@
-/// The error type returned from the `try_indices!` macro.
+/// The error type returned from the `try_indices!` macro and the `try_` functions.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum TryIndicesError {
     /// The requested index is larger than the length of the input slice.
-    IndexOutOfBounds,
+    IndexOutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The position of the index in the request.
+        position: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
     /// The index has been requested twice.
-    DuplicateIndex,
+    DuplicateIndex {
+        /// The requested index.
+        index: usize,
+        /// The position of the first request for the index.
+        first: usize,
+        /// The position of the second request for the index.
+        second: usize,
+    },
+    /// The requested range is decreasing or extends past the end of the input slice.
+    InvalidRange {
+        /// The start of the requested range.
+        start: usize,
+        /// The exclusive end of the requested range.
+        end: usize,
+        /// The position of the range in the request.
+        position: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
 }
 
-impl std::error::Error for TryIndicesError {}
+impl core::error::Error for TryIndicesError {}
 
 impl core::fmt::Display for TryIndicesError {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let variant_name = match *self {
-            TryIndicesError::IndexOutOfBounds => "TryIndicesError::IndexOutOfBounds",
-            TryIndicesError::DuplicateIndex => "TryIndicesError::DuplicateIndex",
-        };
-        write!(f, "{}", variant_name)
+        match *self {
+            TryIndicesError::IndexOutOfBounds {
+                index,
+                position,
+                len,
+            } => write!(
+                f,
+                "Index out of bounds. Requested index was `{}` at position `{}` while slice length was `{}`.",
+                index, position, len
+            ),
+            TryIndicesError::DuplicateIndex {
+                index,
+                first,
+                second,
+            } => write!(
+                f,
+                "Duplicate indices are not allowed. Index `{}` was requested at positions `{}` and `{}`.",
+                index, first, second
+            ),
+            TryIndicesError::InvalidRange {
+                start,
+                end,
+                position,
+                len,
+            } => write!(
+                f,
+                "Invalid range. Requested range was `{}..{}` at position `{}` while slice length was `{}`.",
+                start, end, position, len
+            ),
+        }
     }
 }
 
 /// The error type returned from the `try_indices_ordered!` macro.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum TryIndicesOrderedError {
     /// The requested index is larger than the length of the input slice.
-    IndexOutOfBounds,
-    /// The input indices are either not sorted or out of bounds.
-    InvalidIndex,
+    IndexOutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The position of the index in the request.
+        position: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// The index has been requested twice.
+    DuplicateIndex {
+        /// The requested index.
+        index: usize,
+        /// The position of the first request for the index.
+        first: usize,
+        /// The position of the second request for the index.
+        second: usize,
+    },
+    /// The index is smaller than the index requested before it.
+    NotSorted {
+        /// The requested index.
+        index: usize,
+        /// The position of the index in the request.
+        position: usize,
+        /// The index requested at the previous position.
+        previous: usize,
+    },
+    /// The requested range is decreasing or extends past the end of the input slice.
+    InvalidRange {
+        /// The start of the requested range.
+        start: usize,
+        /// The exclusive end of the requested range.
+        end: usize,
+        /// The position of the range in the request.
+        position: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
 }
 
-impl std::error::Error for TryIndicesOrderedError {}
+impl core::error::Error for TryIndicesOrderedError {}
 
 impl core::fmt::Display for TryIndicesOrderedError {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let variant_name = match *self {
-            TryIndicesOrderedError::IndexOutOfBounds => "TryIndicesOrderedError::IndexOutOfBounds",
-            TryIndicesOrderedError::InvalidIndex => "TryIndicesOrderedError::InvalidIndex",
-        };
-        write!(f, "{}", variant_name)
+        match *self {
+            TryIndicesOrderedError::IndexOutOfBounds {
+                index,
+                position,
+                len,
+            } => write!(
+                f,
+                "Index out of bounds. Requested index was `{}` at position `{}` while slice length was `{}`.",
+                index, position, len
+            ),
+            TryIndicesOrderedError::DuplicateIndex {
+                index,
+                first,
+                second,
+            } => write!(
+                f,
+                "Duplicate indices are not allowed. Index `{}` was requested at positions `{}` and `{}`.",
+                index, first, second
+            ),
+            TryIndicesOrderedError::NotSorted {
+                index,
+                position,
+                previous,
+            } => write!(
+                f,
+                "Indices not sorted. Requested index `{}` at position `{}` is smaller than the previous index `{}`.",
+                index, position, previous
+            ),
+            TryIndicesOrderedError::InvalidRange {
+                start,
+                end,
+                position,
+                len,
+            } => write!(
+                f,
+                "Invalid range. Requested range was `{}..{}` at position `{}` while slice length was `{}`.",
+                start, end, position, len
+            ),
+        }
+    }
+}
+
+/// The error type returned from the `try_indices_map!` macro and `try_indices_map`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryIndicesMapError {
+    /// The requested key is not in the map.
+    MissingKey {
+        /// The position of the key in the request.
+        position: usize,
+    },
+    /// The key has been requested twice.
+    DuplicateKey {
+        /// The position of the first request for the key.
+        first: usize,
+        /// The position of the second request for the key.
+        second: usize,
+    },
+}
+
+impl core::error::Error for TryIndicesMapError {}
+
+impl core::fmt::Display for TryIndicesMapError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryIndicesMapError::MissingKey { position } => write!(
+                f,
+                "Missing key. Requested key at position `{}` is not in the map.",
+                position
+            ),
+            TryIndicesMapError::DuplicateKey { first, second } => write!(
+                f,
+                "Duplicate keys are not allowed. The same key was requested at positions `{}` and `{}`.",
+                first, second
+            ),
+        }
+    }
+}
+
+/// The error type returned from `Arena::try_get_many_mut` and `try_get_many_mut!`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryArenaError {
+    /// The requested key was never issued by the arena.
+    MissingKey {
+        /// The position of the key in the request.
+        position: usize,
+    },
+    /// The value of the requested key has been removed, and its slot may have been reused.
+    StaleKey {
+        /// The position of the key in the request.
+        position: usize,
+        /// The generation of the requested key.
+        generation: u64,
+        /// The current generation of the key's slot.
+        current: u64,
+    },
+    /// The key has been requested twice.
+    DuplicateKey {
+        /// The position of the first request for the key.
+        first: usize,
+        /// The position of the second request for the key.
+        second: usize,
+    },
+}
+
+impl core::error::Error for TryArenaError {}
+
+impl core::fmt::Display for TryArenaError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryArenaError::MissingKey { position } => write!(
+                f,
+                "Missing key. Requested key at position `{}` is not in the arena.",
+                position
+            ),
+            TryArenaError::StaleKey {
+                position,
+                generation,
+                current,
+            } => write!(
+                f,
+                "Stale key. Requested key at position `{}` has generation `{}` while its slot has generation `{}`.",
+                position, generation, current
+            ),
+            TryArenaError::DuplicateKey { first, second } => write!(
+                f,
+                "Duplicate keys are not allowed. The same key was requested at positions `{}` and `{}`.",
+                first, second
+            ),
+        }
+    }
+}
+
+/// The error type yielded by `IndicesIter`. Since the iterator only tracks which indices have been
+/// claimed, a duplicate is reported by the position of its repeated request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryIndicesIterError {
+    /// The requested index is larger than the length of the input slice.
+    IndexOutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The position of the index in the request.
+        position: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// The index has already been requested.
+    DuplicateIndex {
+        /// The requested index.
+        index: usize,
+        /// The position of the repeated request for the index.
+        position: usize,
+    },
+}
+
+impl core::error::Error for TryIndicesIterError {}
+
+impl core::fmt::Display for TryIndicesIterError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryIndicesIterError::IndexOutOfBounds {
+                index,
+                position,
+                len,
+            } => write!(
+                f,
+                "Index out of bounds. Requested index was `{}` at position `{}` while slice length was `{}`.",
+                index, position, len
+            ),
+            TryIndicesIterError::DuplicateIndex { index, position } => write!(
+                f,
+                "Duplicate indices are not allowed. Index `{}` at position `{}` was already requested.",
+                index, position
+            ),
+        }
+    }
+}
+
+/// The error type returned from the `try_indices_nd!` macro and `try_indices_nd`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryIndicesNdError {
+    /// The number of elements in the shape is not the length of the input slice.
+    ShapeMismatch {
+        /// The number of elements in the shape, saturating at `usize::MAX`.
+        elements: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// The coordinate on one axis is not smaller than the size of that axis.
+    CoordinateOutOfBounds {
+        /// The requested coordinate on the axis.
+        coordinate: usize,
+        /// The axis, starting from `0` for the outermost.
+        axis: usize,
+        /// The position of the coordinates in the request.
+        position: usize,
+        /// The size of the axis.
+        size: usize,
+    },
+    /// The same coordinates have been requested twice.
+    DuplicateCoordinates {
+        /// The position of the first request for the coordinates.
+        first: usize,
+        /// The position of the second request for the coordinates.
+        second: usize,
+    },
+}
+
+impl core::error::Error for TryIndicesNdError {}
+
+impl core::fmt::Display for TryIndicesNdError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryIndicesNdError::ShapeMismatch { elements, len } => write!(
+                f,
+                "Shape does not match the slice. The shape has `{}` elements while slice length was `{}`.",
+                elements, len
+            ),
+            TryIndicesNdError::CoordinateOutOfBounds {
+                coordinate,
+                axis,
+                position,
+                size,
+            } => write!(
+                f,
+                "Coordinate out of bounds. Requested coordinate was `{}` on axis `{}` at position `{}` while axis size was `{}`.",
+                coordinate, axis, position, size
+            ),
+            TryIndicesNdError::DuplicateCoordinates { first, second } => write!(
+                f,
+                "Duplicate coordinates are not allowed. The same coordinates were requested at positions `{}` and `{}`.",
+                first, second
+            ),
+        }
+    }
+}
+
+/// The error type returned from `try_for_each_pair_mut` and `try_for_each_pair_or_self_mut`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryEdgesError {
+    /// An endpoint of the edge is larger than the length of the input slice.
+    IndexOutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The position of the edge in the request.
+        edge: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// Both endpoints of the edge are the same index and the policy is `SelfLoops::Error`.
+    SelfLoop {
+        /// The requested index.
+        index: usize,
+        /// The position of the edge in the request.
+        edge: usize,
+    },
+}
+
+impl core::error::Error for TryEdgesError {}
+
+impl core::fmt::Display for TryEdgesError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryEdgesError::IndexOutOfBounds { index, edge, len } => write!(
+                f,
+                "Index out of bounds. Requested index was `{}` in the edge at position `{}` while slice length was `{}`.",
+                index, edge, len
+            ),
+            TryEdgesError::SelfLoop { index, edge } => write!(
+                f,
+                "Self-loops are not allowed. Index `{}` was requested twice in the edge at position `{}`.",
+                index, edge
+            ),
+        }
+    }
+}
+
+/// The error type returned from `Stencil::try_apply`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TryStencilError {
+    /// The length of the input slice is not a multiple of the number of columns of the grid.
+    ShapeMismatch {
+        /// The number of columns of the grid.
+        columns: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// The requested centre is larger than the length of the input slice.
+    CentreOutOfBounds {
+        /// The requested centre.
+        centre: usize,
+        /// The length of the input slice.
+        len: usize,
+    },
+    /// The neighbour is off the edge of the grid and the edge policy is `EdgePolicy::Error`.
+    OffEdge {
+        /// The requested centre.
+        centre: usize,
+        /// The position of the offset in the stencil.
+        position: usize,
+    },
+    /// Two offsets reach the same element, e.g. because of clamping or wrapping at an edge.
+    DuplicateIndex {
+        /// The index reached by both offsets.
+        index: usize,
+        /// The position of the first offset in the stencil.
+        first: usize,
+        /// The position of the second offset in the stencil.
+        second: usize,
+    },
+}
+
+impl core::error::Error for TryStencilError {}
+
+impl core::fmt::Display for TryStencilError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            TryStencilError::ShapeMismatch { columns, len } => write!(
+                f,
+                "Grid does not match the slice. Slice length was `{}` while the grid has `{}` columns.",
+                len, columns
+            ),
+            TryStencilError::CentreOutOfBounds { centre, len } => write!(
+                f,
+                "Centre out of bounds. Requested centre was `{}` while slice length was `{}`.",
+                centre, len
+            ),
+            TryStencilError::OffEdge { centre, position } => write!(
+                f,
+                "Neighbour off the edge. The offset at position `{}` leaves the grid from centre `{}`.",
+                position, centre
+            ),
+            TryStencilError::DuplicateIndex {
+                index,
+                first,
+                second,
+            } => write!(
+                f,
+                "Duplicate indices are not allowed. Index `{}` was reached by the offsets at positions `{}` and `{}`.",
+                index, first, second
+            ),
+        }
     }
 }
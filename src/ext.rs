@@ -0,0 +1,272 @@
+use crate::{
+    indices_array, indices_array_ordered, try_indices_array, try_indices_array_ordered,
+    TryIndicesError, TryIndicesOrderedError,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+    indices_slice, indices_slices, indices_slices_vec, try_indices_slice, try_indices_slices,
+    try_indices_slices_vec,
+};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+/// Provides the crate's functions as methods, e.g. `vec.get_indices_mut([4, 1])`.
+/// Implemented for `[T]`, `[T; N]`, `Vec<T>`, and `Box<[T]>`.
+pub trait IndicesExt<T> {
+    /// Returns the elements the other methods retrieve from.
+    fn indices_elements_mut(&mut self) -> &mut [T];
+
+    /// Returns mutable references for the requested indices.
+    /// Panics if any index is out of bounds or duplicated. See `indices_array`.
+    #[inline]
+    fn get_indices_mut<const N: usize>(&mut self, indices: [usize; N]) -> [&mut T; N] {
+        indices_array(self.indices_elements_mut(), &indices)
+    }
+
+    /// Returns mutable references for the requested indices.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_array`.
+    #[inline]
+    fn try_get_indices_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Result<[&mut T; N], TryIndicesError> {
+        try_indices_array(self.indices_elements_mut(), &indices)
+    }
+
+    /// Returns mutable references for the requested indices, which must be ordered smallest to largest.
+    /// Panics if the requested indices are not smallest to largest, or if any index is duplicated or out of bounds.
+    /// See `indices_array_ordered`.
+    #[inline]
+    fn get_indices_ordered_mut<const N: usize>(&mut self, indices: [usize; N]) -> [&mut T; N] {
+        indices_array_ordered(self.indices_elements_mut(), &indices)
+    }
+
+    /// Returns mutable references for the requested indices, which must be ordered smallest to largest.
+    /// Returns `TryIndicesOrderedError` if the requested indices are not smallest to largest, or if any index is
+    /// duplicated or out of bounds. See `try_indices_array_ordered`.
+    #[inline]
+    fn try_get_indices_ordered_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Result<[&mut T; N], TryIndicesOrderedError> {
+        try_indices_array_ordered(self.indices_elements_mut(), &indices)
+    }
+
+    /// Returns mutable references for a number of indices only known at runtime.
+    /// Panics if any index is out of bounds or duplicated. See `indices_slice`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn get_indices_slice_mut(&mut self, indices: &[usize]) -> Vec<&mut T> {
+        indices_slice(self.indices_elements_mut(), indices)
+    }
+
+    /// Returns mutable references for a number of indices only known at runtime.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_slice`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_get_indices_slice_mut(
+        &mut self,
+        indices: &[usize],
+    ) -> Result<Vec<&mut T>, TryIndicesError> {
+        try_indices_slice(self.indices_elements_mut(), indices)
+    }
+
+    /// Returns mutable references for each group of requested indices.
+    /// Panics if any index is out of bounds or duplicated across all groups. See `indices_slices`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn get_indices_slices_mut<const N: usize>(
+        &mut self,
+        indices: [&[usize]; N],
+    ) -> [Vec<&mut T>; N] {
+        indices_slices(self.indices_elements_mut(), indices)
+    }
+
+    /// Returns mutable references for each group of requested indices.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated across all groups. See `try_indices_slices`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_get_indices_slices_mut<const N: usize>(
+        &mut self,
+        indices: [&[usize]; N],
+    ) -> Result<[Vec<&mut T>; N], TryIndicesError> {
+        try_indices_slices(self.indices_elements_mut(), indices)
+    }
+
+    /// Returns mutable references for each group of requested indices, for a number of groups only
+    /// known at runtime.
+    /// Panics if any index is out of bounds or duplicated across all groups. See `indices_slices_vec`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn get_indices_slices_vec_mut(&mut self, indices: &[&[usize]]) -> Vec<Vec<&mut T>> {
+        indices_slices_vec(self.indices_elements_mut(), indices)
+    }
+
+    /// Returns mutable references for each group of requested indices, for a number of groups only
+    /// known at runtime.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated across all groups. See `try_indices_slices_vec`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_get_indices_slices_vec_mut(
+        &mut self,
+        indices: &[&[usize]],
+    ) -> Result<Vec<Vec<&mut T>>, TryIndicesError> {
+        try_indices_slices_vec(self.indices_elements_mut(), indices)
+    }
+}
+
+impl<T> IndicesExt<T> for [T] {
+    #[inline]
+    fn indices_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> IndicesExt<T> for [T; N] {
+    #[inline]
+    fn indices_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IndicesExt<T> for Vec<T> {
+    #[inline]
+    fn indices_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IndicesExt<T> for Box<[T]> {
+    #[inline]
+    fn indices_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IndicesExt, TryIndicesError, TryIndicesOrderedError};
+
+    #[test]
+    fn slice_get_indices_mut() {
+        let mut data = [5, 4, 3, 2, 1];
+        let [one, two] = data.as_mut_slice().get_indices_mut([3, 1]);
+        *one = 10;
+        *two = 20;
+        assert_eq!(data, [5, 20, 3, 10, 1]);
+    }
+
+    #[test]
+    fn array_try_get_indices_mut() {
+        let mut data = [5, 4, 3, 2, 1];
+        let [one, two] = data.try_get_indices_mut([0, 4]).unwrap();
+        std::mem::swap(one, two);
+        assert_eq!(data, [1, 4, 3, 2, 5]);
+        assert_eq!(
+            data.try_get_indices_mut([2, 2]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 2,
+                first: 0,
+                second: 1
+            })
+        );
+    }
+
+    #[test]
+    fn array_get_indices_ordered_mut() {
+        let mut data = [5, 4, 3, 2, 1];
+        let [one, two, three] = data.get_indices_ordered_mut([0, 2, 4]);
+        *one += 1;
+        *two += 1;
+        *three += 1;
+        assert_eq!(data, [6, 4, 4, 2, 2]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn array_get_indices_ordered_mut_out_of_order() {
+        let mut data = [5, 4, 3, 2, 1];
+        let [_one, _two] = data.get_indices_ordered_mut([2, 1]);
+    }
+
+    #[test]
+    fn array_try_get_indices_ordered_mut() {
+        let mut data = [5, 4, 3, 2, 1];
+        assert_eq!(
+            data.try_get_indices_ordered_mut([2, 1]),
+            Err(TryIndicesOrderedError::NotSorted {
+                index: 1,
+                position: 1,
+                previous: 2
+            })
+        );
+        assert_eq!(
+            data.try_get_indices_ordered_mut([2, 5]),
+            Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_get_indices_mut() {
+        let mut data = vec![5, 4, 3, 2, 1];
+        let [one, two] = data.get_indices_mut([4, 0]);
+        *one = 10;
+        *two = 20;
+        assert_eq!(data, [20, 4, 3, 2, 10]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_get_indices_slice_mut() {
+        let mut data = vec![5, 4, 3, 2, 1];
+        let indices = vec![3, 1, 2];
+        for element in data.get_indices_slice_mut(&indices) {
+            *element *= 10;
+        }
+        assert_eq!(data, [5, 40, 30, 20, 1]);
+        assert_eq!(
+            data.try_get_indices_slice_mut(&[1, 9]),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 9,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_slice_get_indices_slices_mut() {
+        let mut data: Box<[i32]> = vec![5, 4, 3, 2, 1].into_boxed_slice();
+        let [mut left, right] = data.get_indices_slices_mut([&[0, 1], &[4]]);
+        *left[1] += *right[0];
+        assert_eq!(*data, [5, 5, 3, 2, 1]);
+        assert_eq!(
+            data.try_get_indices_slices_mut([&[0, 1], &[1]])
+                .unwrap_err(),
+            TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 1,
+                second: 2
+            }
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_get_indices_slices_vec_mut() {
+        let mut data = vec![5, 4, 3, 2, 1];
+        let mut groups = data.get_indices_slices_vec_mut(&[&[0], &[3, 1], &[]]);
+        *groups[0][0] += *groups[1][1];
+        assert_eq!(groups[2].len(), 0);
+        assert_eq!(data, [9, 4, 3, 2, 1]);
+        assert!(data.try_get_indices_slices_vec_mut(&[&[2], &[2]]).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::TryIndicesIterError;
+use alloc::vec::Vec;
+use core::{iter::FusedIterator, marker::PhantomData};
+
+/// Returns an iterator over mutable references for the indices yielded by `indices`, which may be
+/// any `IntoIterator<Item = usize>`. Indices are validated as they are reached, so the whole request
+/// is never materialised.
+///
+/// The iterator yields `Err` for the first out of bounds or duplicate index and then ends. Use
+/// [`IndicesIter::skip_duplicates`] to skip duplicate indices instead.
+pub fn indices_iter<'a, T, I>(slice: &'a mut [T], indices: I) -> IndicesIter<'a, T, I::IntoIter>
+where
+    I: IntoIterator<Item = usize>,
+{
+    IndicesIter {
+        ptr: slice.as_mut_ptr(),
+        len: slice.len(),
+        indices: indices.into_iter(),
+        position: 0,
+        claimed: Vec::new(),
+        skip_duplicates: false,
+        done: false,
+        marker: PhantomData,
+    }
+}
+
+/// Iterator over mutable references for lazily validated indices. Created by [`indices_iter`].
+///
+/// Claimed indices are tracked in a bitset that grows to the largest index requested.
+pub struct IndicesIter<'a, T, I> {
+    ptr: *mut T,
+    len: usize,
+    indices: I,
+    /// The position of the next index in the request.
+    position: usize,
+    claimed: Vec<u64>,
+    skip_duplicates: bool,
+    done: bool,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<T: Send, I: Send> Send for IndicesIter<'_, T, I> {}
+unsafe impl<T: Sync, I: Sync> Sync for IndicesIter<'_, T, I> {}
+
+impl<T, I> IndicesIter<'_, T, I> {
+    /// Skips indices that have already been yielded instead of ending with an error. Out of
+    /// bounds indices still end the iterator with an error.
+    #[inline]
+    pub fn skip_duplicates(mut self) -> Self {
+        self.skip_duplicates = true;
+        self
+    }
+}
+
+impl<'a, T, I> Iterator for IndicesIter<'a, T, I>
+where
+    I: Iterator<Item = usize>,
+{
+    type Item = Result<&'a mut T, TryIndicesIterError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            let Some(index) = self.indices.next() else {
+                self.done = true;
+                return None;
+            };
+            let position = self.position;
+            self.position += 1;
+            if index >= self.len {
+                self.done = true;
+                return Some(Err(TryIndicesIterError::IndexOutOfBounds {
+                    index,
+                    position,
+                    len: self.len,
+                }));
+            }
+            let word = index / 64;
+            let bit = 1 << (index % 64);
+            if word >= self.claimed.len() {
+                self.claimed.resize(word + 1, 0);
+            }
+            if self.claimed[word] & bit != 0 {
+                if self.skip_duplicates {
+                    continue;
+                }
+                self.done = true;
+                return Some(Err(TryIndicesIterError::DuplicateIndex { index, position }));
+            }
+            self.claimed[word] |= bit;
+            // Each in bounds index is yielded at most once.
+            return Some(Ok(unsafe { &mut *self.ptr.add(index) }));
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.indices.size_hint();
+        // An error ends the iterator early, and skipped duplicates may leave nothing to yield.
+        if self.skip_duplicates {
+            (0, upper)
+        } else {
+            (lower.min(1), upper)
+        }
+    }
+}
+
+impl<T, I> FusedIterator for IndicesIter<'_, T, I> where I: Iterator<Item = usize> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices_iter, TryIndicesIterError};
+    use std::collections::HashSet;
+
+    #[test]
+    fn indices_iter_ranges_and_adaptors() {
+        let mut data: Vec<usize> = (0..200).collect();
+        for element in indices_iter(&mut data, (0..200).step_by(3).filter(|i| i % 2 == 0)) {
+            *element.unwrap() = 0;
+        }
+        assert!(data
+            .iter()
+            .enumerate()
+            .all(|(i, e)| (*e == 0) == (i % 6 == 0)));
+    }
+
+    #[test]
+    fn indices_iter_hash_set() {
+        let mut data = [5, 4, 3, 2, 1];
+        let set: HashSet<usize> = [4, 0, 2].into_iter().collect();
+        let elements: Result<Vec<&mut i32>, _> = indices_iter(&mut data, set).collect();
+        for element in elements.unwrap() {
+            *element *= 10;
+        }
+        assert_eq!(data, [50, 4, 30, 2, 10]);
+    }
+
+    #[test]
+    fn indices_iter_is_lazy() {
+        let mut data = vec![0u8; 1 << 20];
+        let mut iter = indices_iter(&mut data, (0..).map(|i| i * 1000));
+        let first = iter.next().unwrap().unwrap();
+        let second = iter.next().unwrap().unwrap();
+        *first = 1;
+        *second = 2;
+        assert_eq!(iter.claimed.len(), 16);
+        assert_eq!((data[0], data[1000]), (1, 2));
+    }
+
+    #[test]
+    fn indices_iter_size_hint() {
+        let mut data = [5, 4, 3, 2, 1];
+        let mut iter = indices_iter(&mut data, [0, 1, 1, 2, 3]);
+        assert_eq!(iter.size_hint(), (1, Some(5)));
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = indices_iter(&mut data, [0, 1, 1, 2, 3]).skip_duplicates();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(3)));
+        assert_eq!(iter.count(), 2);
+    }
+
+    #[test]
+    fn indices_iter_fused_error() {
+        let mut data = [5, 4, 3, 2, 1];
+        let mut iter = indices_iter(&mut data, [1, 3, 1, 4]);
+        assert_eq!(iter.next(), Some(Ok(&mut 4)));
+        assert_eq!(iter.next(), Some(Ok(&mut 2)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(TryIndicesIterError::DuplicateIndex {
+                index: 1,
+                position: 2
+            }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = indices_iter(&mut data, [0, 7, 1]);
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next(),
+            Some(Err(TryIndicesIterError::IndexOutOfBounds {
+                index: 7,
+                position: 1,
+                len: 5
+            }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn indices_iter_skip_duplicates() {
+        let mut data = [5, 4, 3, 2, 1];
+        let elements: Vec<_> = indices_iter(&mut data, [3, 3, 0, 3, 0, 4])
+            .skip_duplicates()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(elements.len(), 3);
+        for element in elements {
+            *element = 0;
+        }
+        assert_eq!(data, [0, 4, 3, 0, 0]);
+
+        let mut iter = indices_iter(&mut data, [2, 2, 9]).skip_duplicates();
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next(),
+            Some(Err(TryIndicesIterError::IndexOutOfBounds {
+                index: 9,
+                position: 2,
+                len: 5
+            }))
+        );
+    }
+}
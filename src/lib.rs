@@ -1,133 +1,185 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod arena;
+mod check;
+mod combinations;
+mod const_indices;
+mod disjoint;
+mod edges;
 mod errors;
-
+mod ext;
+#[cfg(feature = "alloc")]
+mod iter;
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(test)]
+mod miri_tests;
+mod nd;
+mod request;
+mod scratch;
+// Only public for the benchmarks, which compare the kernels with their scalar fallbacks.
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub mod simd;
+#[cfg(not(feature = "bench-internals"))]
+mod simd;
+mod split;
+mod stencil;
+mod typed;
+#[cfg(feature = "alloc")]
+mod vec_deque;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use check::{check_indices, check_indices_ordered};
+
+#[cfg(feature = "alloc")]
+pub use arena::{Arena, ArenaKey};
+#[doc(hidden)]
+pub use check::{check_requests, check_requests_mixed, check_requests_ordered};
+pub use combinations::{for_each_combination_mut, pairs_mut};
+pub use const_indices::{get_const_mut, ConstIndices};
+pub use disjoint::DisjointIndices;
+#[cfg(feature = "alloc")]
+pub use disjoint::DisjointIndicesVec;
+pub use edges::{
+    for_each_pair_mut, for_each_pair_or_self_mut, try_for_each_pair_mut,
+    try_for_each_pair_or_self_mut, SelfLoops,
+};
 pub use errors::*;
+pub use ext::IndicesExt;
+#[cfg(feature = "alloc")]
+pub use iter::{indices_iter, IndicesIter};
+#[cfg(feature = "alloc")]
+pub use map::{indices_map, try_indices_map, IndicesMap};
+pub use nd::{indices_nd, try_indices_nd};
+#[doc(hidden)]
+pub use request::{Claimer, GenericRequest, RequestOf, Span};
+pub use request::{IndexRequest, IndexType};
+#[cfg(feature = "alloc")]
+pub use scratch::IndicesScratch;
+pub use scratch::{
+    indices_slice_with, indices_slices_with, try_indices_slice_with, try_indices_slices_with,
+    IndicesSliceIter, IndicesSliceMut,
+};
+pub use split::{split_array_mut, sub_arrays_mut};
+pub use stencil::{EdgePolicy, Stencil};
+pub use typed::TypedSlice;
+#[cfg(feature = "alloc")]
+pub use vec_deque::{indices_vec_deque, try_indices_vec_deque};
 
 /// Returns mutable references for the requested indices in the provided slice.
 /// Panics if any index is out of bounds or duplicate indices.
+#[cfg(feature = "alloc")]
 pub fn indices_slice<'a, T>(slice: &'a mut [T], indices: &[usize]) -> Vec<&'a mut T> {
-    let slice_length = slice.len();
-    let indices_length = indices.len();
-    if slice_length == 0 {
-        if indices_length != 0 {
-            panic!("Requested indices but slice is empty.")
-        }
-        return Vec::new();
-    }
-    if indices_length == 0 {
-        return Vec::new();
-    }
-    let mut check: Vec<usize> = indices.to_vec();
-    insertion_sort(&mut check);
-    let indices_len_minus_one = indices_length - 1;
-    let slice_len_minus_one = slice_length - 1;
-    for i in 0..indices_len_minus_one {
-        if check[i] == check[i + 1] {
-            panic!(
-                "Duplicate indices are not allowed. Index `{}` was requested twice.",
-                check[i]
-            );
-        }
+    match try_indices_slice(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
-    if check[indices_len_minus_one] > slice_len_minus_one {
-        panic!(
-            "Index out of bounds. Requested index was `{}` while slice length was `{}`.",
-            check[indices_len_minus_one],
-            slice_len_minus_one + 1
-        );
+}
+
+/// Returns mutable references for the requested indices in the provided slice.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
+#[cfg(feature = "alloc")]
+pub fn try_indices_slice<'a, T>(
+    slice: &'a mut [T],
+    indices: &[usize],
+) -> Result<Vec<&'a mut T>, TryIndicesError> {
+    if indices.is_empty() {
+        return Ok(Vec::new());
     }
-    let mut vector: Vec<std::mem::MaybeUninit<*mut T>> =
-        vec![std::mem::MaybeUninit::uninit(); indices_length];
-    let ptr = slice.as_mut_ptr();
-    unsafe {
-        for (i, index) in indices.iter().enumerate() {
-            vector[i].write(ptr.add(*index));
-        }
-        std::mem::transmute::<_, Vec<&'a mut T>>(vector)
+    check::check_indices_alloc(indices, slice.len())?;
+    Ok(unsafe { claim_vec(slice, indices) })
+}
+
+/// Returns mutable references for the requested indices of any `IndexType`, e.g. typed ids, in
+/// the provided slice.
+/// Panics if any index is out of bounds or duplicate indices. See `indices_slice`.
+#[cfg(feature = "alloc")]
+pub fn indices_slice_typed<'a, T, I: IndexType>(
+    slice: &'a mut [T],
+    indices: &[I],
+) -> Vec<&'a mut T> {
+    match try_indices_slice_typed(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
 }
 
+/// Returns mutable references for the requested indices of any `IndexType`, e.g. typed ids, in
+/// the provided slice.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_slice`.
+#[cfg(feature = "alloc")]
+pub fn try_indices_slice_typed<'a, T, I: IndexType>(
+    slice: &'a mut [T],
+    indices: &[I],
+) -> Result<Vec<&'a mut T>, TryIndicesError> {
+    let indices: Vec<usize> = indices.iter().map(|index| index.to_index()).collect();
+    try_indices_slice(slice, &indices)
+}
+
 /// Returns mutable references for the requested indices in the provided slices.
 /// Panics if any index is out of bounds or duplicate indices.
+#[cfg(feature = "alloc")]
 pub fn indices_slices<'a, T, const N: usize>(
     slice: &'a mut [T],
     indices: [&[usize]; N],
 ) -> [Vec<&'a mut T>; N] {
-    const {
-        assert!(
-            std::mem::size_of::<[std::mem::MaybeUninit<Vec<*mut T>>; N]>()
-                == std::mem::size_of::<[Vec<*mut T>; N]>()
-        );
-    }
-    if N == 0 {
-        return unsafe { std::mem::zeroed() };
-    }
-    let slice_length = slice.len();
-    let mut all_requested_indices: Vec<usize> = indices.concat();
-    let all_requested_indices_length = all_requested_indices.len();
-    if slice_length == 0 {
-        if all_requested_indices_length != 0 {
-            panic!("Requested indices but slice is empty.")
-        }
-        unsafe {
-            let mut array: [std::mem::MaybeUninit<Vec<*mut T>>; N] =
-                std::mem::MaybeUninit::uninit().assume_init();
-            for i in 0..N {
-                let out_vec = Vec::with_capacity(0);
-                array[i].write(out_vec);
-            }
-            return std::mem::transmute_copy::<
-                [std::mem::MaybeUninit<Vec<*mut T>>; N],
-                [Vec<&'a mut T>; N],
-            >(&array);
-        }
-    }
-    if all_requested_indices_length == 0 {
-        unsafe {
-            let mut array: [std::mem::MaybeUninit<Vec<*mut T>>; N] =
-                std::mem::MaybeUninit::uninit().assume_init();
-            for i in 0..N {
-                let out_vec = Vec::with_capacity(0);
-                array[i].write(out_vec);
-            }
-            return std::mem::transmute_copy::<
-                [std::mem::MaybeUninit<Vec<*mut T>>; N],
-                [Vec<&'a mut T>; N],
-            >(&array);
-        }
+    match try_indices_slices(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
-    insertion_sort(&mut all_requested_indices);
-    let indices_len_minus_one = all_requested_indices_length - 1;
-    let slice_len_minus_one = slice_length - 1;
-    for i in 0..indices_len_minus_one {
-        if all_requested_indices[i] == all_requested_indices[i + 1] {
-            panic!(
-                "Duplicate indices are not allowed. Index `{}` was requested twice.",
-                all_requested_indices[i]
-            );
-        }
-    }
-    if all_requested_indices[indices_len_minus_one] > slice_len_minus_one {
-        panic!(
-            "Index out of bounds. Requested index was `{}` while slice length was `{}`.",
-            all_requested_indices[indices_len_minus_one],
-            slice_len_minus_one + 1
-        );
+}
+
+/// Returns mutable references for the requested indices in the provided slices.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated. Positions reported in the
+/// error count across all the requested slices, in order.
+#[cfg(feature = "alloc")]
+pub fn try_indices_slices<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: [&[usize]; N],
+) -> Result<[Vec<&'a mut T>; N], TryIndicesError> {
+    let groups = try_indices_slices_vec(slice, &indices)?;
+    // There is one group per requested slice.
+    Ok(groups.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+/// Returns mutable references for each group of requested indices, for a number of groups only
+/// known at runtime, e.g. one group per worker.
+/// Panics if any index is out of bounds or duplicated across all groups.
+#[cfg(feature = "alloc")]
+pub fn indices_slices_vec<'a, T>(slice: &'a mut [T], indices: &[&[usize]]) -> Vec<Vec<&'a mut T>> {
+    match try_indices_slices_vec(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
+}
+
+/// Returns mutable references for each group of requested indices, for a number of groups only
+/// known at runtime, e.g. one group per worker.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated. Positions reported in the
+/// error count across all the requested groups, in order.
+#[cfg(feature = "alloc")]
+pub fn try_indices_slices_vec<'a, T>(
+    slice: &'a mut [T],
+    indices: &[&[usize]],
+) -> Result<Vec<Vec<&'a mut T>>, TryIndicesError> {
+    let all_requested_indices: Vec<usize> = indices.concat();
+    check::check_indices_alloc(&all_requested_indices, slice.len())?;
     let ptr = slice.as_mut_ptr();
-    unsafe {
-        let mut array: [std::mem::MaybeUninit<Vec<*mut T>>; N] =
-            std::mem::MaybeUninit::uninit().assume_init();
-        for (i, indice) in indices.iter().enumerate() {
-            let mut out_vec = Vec::with_capacity(indice.len());
-            for index in *indice {
-                out_vec.push(ptr.add(*index));
-            }
-            array[i].write(out_vec);
-        }
-        std::mem::transmute_copy::<[std::mem::MaybeUninit<Vec<*mut T>>; N], [Vec<&'a mut T>; N]>(
-            &array,
-        )
-    }
+    Ok(indices
+        .iter()
+        .map(|indices| {
+            indices
+                .iter()
+                .map(|index| unsafe { &mut *ptr.add(*index) })
+                .collect()
+        })
+        .collect())
 }
 
 //************************************************************************//
@@ -138,340 +190,358 @@ pub fn indices_array<'a, T, const N: usize>(
     slice: &'a mut [T],
     indices: &[usize; N],
 ) -> [&'a mut T; N] {
-    const {
-        assert!(
-            std::mem::size_of::<[std::mem::MaybeUninit<*mut T>; N]>()
-                == std::mem::size_of::<[&'a mut T; N]>()
-        );
+    match try_indices_array(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
-    let slice_length = slice.len();
-    let indices_length = N;
-    if slice_length == 0 {
-        if indices_length != 0 {
-            panic!("Requested indices but slice is empty.")
-        }
-        return unsafe { std::mem::zeroed() };
-    }
-    if indices_length == 0 {
-        return unsafe { std::mem::zeroed() };
-    }
-    let mut check: Vec<usize> = indices.to_vec();
-    insertion_sort(&mut check);
-    let indices_len_minus_one = indices_length - 1;
-    let slice_len_minus_one = slice_length - 1;
-    for i in 0..indices_len_minus_one {
-        if check[i] == check[i + 1] {
-            panic!(
-                "Duplicate indices are not allowed. Index `{}` was requested twice.",
-                check[i]
-            );
-        }
+}
+
+/// Returns mutable references for the requested indices in the provided array.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
+pub fn try_indices_array<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> Result<[&'a mut T; N], TryIndicesError> {
+    let mut check = *indices;
+    check_indices(indices, &mut check, slice.len())?;
+    Ok(unsafe { claim_array(slice, indices) })
+}
+
+/// Returns mutable references for the requested indices of any `IndexType`, e.g. typed ids, in
+/// the provided array.
+/// Panics if any index is out of bounds or duplicate indices. See `indices_array`.
+pub fn indices_array_typed<'a, T, I: IndexType, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[I; N],
+) -> [&'a mut T; N] {
+    match try_indices_array_typed(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
-    if check[indices_len_minus_one] > slice_len_minus_one {
-        panic!(
-            "Index out of bounds. Requested index was `{}` while slice length was `{}`.",
-            check[indices_len_minus_one],
-            slice_len_minus_one + 1
-        );
+}
+
+/// Returns mutable references for the requested indices of any `IndexType`, e.g. typed ids, in
+/// the provided array.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_array`.
+pub fn try_indices_array_typed<'a, T, I: IndexType, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[I; N],
+) -> Result<[&'a mut T; N], TryIndicesError> {
+    try_indices_array(slice, &indices.map(IndexType::to_index))
+}
+
+/// Returns mutable references for the requested indices in the provided array.
+/// Slightly more efficient than `indices_array` since assumes the requested indices are already ordered smallest to largest.
+/// Panics if the requested indices are not smallest to largest, or if any index is duplicated or out of bounds.
+pub fn indices_array_ordered<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> [&'a mut T; N] {
+    match try_indices_array_ordered(slice, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
     }
+}
+
+/// Returns mutable references for the requested indices in the provided array.
+/// Slightly more efficient than `try_indices_array` since assumes the requested indices are already ordered smallest to largest.
+/// Returns `TryIndicesOrderedError` if the requested indices are not smallest to largest, or if any index is duplicated or out of bounds.
+pub fn try_indices_array_ordered<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> Result<[&'a mut T; N], TryIndicesOrderedError> {
+    check_indices_ordered(indices, slice.len())?;
+    Ok(unsafe { claim_array(slice, indices) })
+}
+
+/// Returns mutable references for the requested indices in the provided slice, without checking
+/// them in release builds. See `indices_slice`.
+///
+/// # Safety
+/// `indices` must be unique and in bounds for `slice`. This is checked with a debug assertion.
+#[cfg(feature = "alloc")]
+pub unsafe fn indices_slice_unchecked<'a, T>(
+    slice: &'a mut [T],
+    indices: &[usize],
+) -> Vec<&'a mut T> {
+    debug_assert_checked(|| check::check_indices_alloc(indices, slice.len()));
+    claim_vec(slice, indices)
+}
 
+/// Returns mutable references for the requested indices in the provided slices, without checking
+/// them in release builds. See `indices_slices`.
+///
+/// # Safety
+/// `indices` must be unique across all slices and in bounds for `slice`. This is checked with a
+/// debug assertion.
+#[cfg(feature = "alloc")]
+pub unsafe fn indices_slices_unchecked<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: [&[usize]; N],
+) -> [Vec<&'a mut T>; N] {
     let ptr = slice.as_mut_ptr();
-    unsafe {
-        let mut array: [std::mem::MaybeUninit<*mut T>; N] =
-            std::mem::MaybeUninit::uninit().assume_init();
-        for (i, index) in indices.iter().enumerate() {
-            array[i].write(ptr.add(*index));
+    let len = slice.len();
+    debug_assert_checked(|| check::check_indices_alloc(&indices.concat(), len));
+    indices.map(|indices| indices.iter().map(|index| &mut *ptr.add(*index)).collect())
+}
+
+/// Returns mutable references for the requested indices in the provided array, without checking
+/// them in release builds. See `indices_array`.
+///
+/// # Safety
+/// `indices` must be unique and in bounds for `slice`. This is checked with a debug assertion.
+pub unsafe fn indices_array_unchecked<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> [&'a mut T; N] {
+    debug_assert_checked(|| {
+        let mut check = *indices;
+        check_indices(indices, &mut check, slice.len())
+    });
+    claim_array(slice, indices)
+}
+
+/// Returns mutable references for the requested indices in the provided array, without checking
+/// them in release builds. See `indices_array_ordered`.
+///
+/// # Safety
+/// `indices` must be ordered smallest to largest, unique and in bounds for `slice`. This is checked
+/// with a debug assertion.
+pub unsafe fn indices_array_ordered_unchecked<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> [&'a mut T; N] {
+    debug_assert_checked(|| check_indices_ordered(indices, slice.len()));
+    claim_array(slice, indices)
+}
+
+/// Panics with the error of `check` in debug builds. `check` is not called in release builds.
+#[inline(always)]
+#[track_caller]
+fn debug_assert_checked<E: core::fmt::Display>(check: impl FnOnce() -> Result<(), E>) {
+    if cfg!(debug_assertions) {
+        if let Err(error) = check() {
+            panic!("{}", error)
         }
-        std::mem::transmute_copy::<[std::mem::MaybeUninit<*mut T>; N], [&'a mut T; N]>(&array)
     }
 }
 
+/// # Safety
+/// `indices` must be unique and in bounds for `slice`.
+unsafe fn claim_array<'a, T, const N: usize>(
+    slice: &'a mut [T],
+    indices: &[usize; N],
+) -> [&'a mut T; N] {
+    let ptr = slice.as_mut_ptr();
+    indices.map(|index| &mut *ptr.add(index))
+}
+
+/// # Safety
+/// `indices` must be unique and in bounds for `slice`.
+#[cfg(feature = "alloc")]
+unsafe fn claim_vec<'a, T>(slice: &'a mut [T], indices: &[usize]) -> Vec<&'a mut T> {
+    let ptr = slice.as_mut_ptr();
+    indices.iter().map(|index| &mut *ptr.add(*index)).collect()
+}
+
 //************************************************************************//
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! to_type {
-    ( $t:expr ) => { &'a mut T };
+macro_rules! next_of {
+    ($iter:ident, $t:expr) => {
+        $iter.next().unwrap()
+    };
 }
 
-/// Returns mutable references for the requested indices.
-/// Panics if any index is out of bounds or duplicated.
+/// Resolves a request, inferring untyped integers as `usize`. See `RequestOf`.
+#[doc(hidden)]
 #[macro_export]
-macro_rules! indices {
-    ($slice:expr, $index1:expr) => {{
-        (&mut $slice[$index1],)
+macro_rules! request_of {
+    ($request:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::GenericRequest as _;
+        $crate::RequestOf($request).request()
     }};
+}
 
-    ($slice:expr, $index1:expr, $index2:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize) -> (&mut T, &mut T) {
-            if one == two {
-                panic!("Duplicate indices are not allowed. Index `{}` was requested twice.", one)
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len {
-                panic!("Index out of bounds.")
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                (&mut *ptr.add(one), &mut *ptr.add(two))
-            }
-        }
-        func($slice, $index1, $index2)
+/// Binds each request to a local once, in order, then checks and claims them all. Requests are
+/// recorded as `mut` or `shared`, and are all claimed mutably unless one of them is `mut`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! claim_requests {
+    (@exclusive mut) => { true };
+    (@exclusive shared) => { false };
+
+    (@claim $claimer:ident $spans:ident mut $request:ident) => {
+        $claimer.claim(&$request, $crate::next_of!($spans, $request))
+    };
+    (@claim $claimer:ident $spans:ident shared $request:ident) => {
+        $claimer.claim_shared(&$request, $crate::next_of!($spans, $request))
+    };
+
+    ($mode:ident $check:path $(| $mixed:path)?; $slice:expr; [$($kind:tt $request:ident)*]; $(mut)? - $head:literal $(, $($tail:tt)+)?) => {
+        compile_error!("Indices may not be negative.")
+    };
+
+    ($mode:ident $check:path $(| $mixed:path)?; $slice:expr; [$($kind:tt $request:ident)*]; mut $head:expr $(, $($tail:tt)+)?) => {{
+        let request = $crate::request_of!($head);
+        $crate::claim_requests!($mode $check $(| $mixed)?; $slice; [$($kind $request)* mut request]; $($($tail)+)?)
     }};
 
-    ($slice:expr, $index1:expr, $index2:expr, $index3:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize, three: usize) -> (&mut T, &mut T, &mut T) {
-            if one == two || one == three || two == three {
-                panic!("Duplicate indices are not allowed.")
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len || three >= slice_len {
-                panic!("Index out of bounds.")
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                (&mut *ptr.add(one), &mut *ptr.add(two), &mut *ptr.add(three))
-            }
-        }
-        func($slice, $index1, $index2, $index3)
+    ($mode:ident $check:path $(| $mixed:path)?; $slice:expr; [$($kind:tt $request:ident)*]; $head:expr $(, $($tail:tt)+)?) => {{
+        let request = $crate::request_of!($head);
+        $crate::claim_requests!($mode $check $(| $mixed)?; $slice; [$($kind $request)* shared request]; $($($tail)+)?)
     }};
 
-    ($slice:expr, $index1:expr, $index2:expr, $index3:expr, $index4:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize, three: usize, four: usize) -> (&mut T, &mut T, &mut T, &mut T) {
-            if one == two || one == three || one == four || two == three || two == four || three == four {
-                panic!("Duplicate indices are not allowed.")
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len || three >= slice_len || four >= slice_len {
-                panic!("Index out of bounds.")
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                (&mut *ptr.add(one), &mut *ptr.add(two), &mut *ptr.add(three), &mut *ptr.add(four))
-            }
+    (panic $check:path $(| $mixed:path)?; $slice:expr; [$(shared $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        if let Err(error) = $check(&spans, claimer.len()) {
+            panic!("{}", error)
         }
-        func($slice, $index1, $index2, $index3, $index4)
+        let mut spans = spans.into_iter();
+        unsafe { ($(claimer.claim(&$request, $crate::next_of!(spans, $request)),)+) }
     }};
 
-    ($slice:expr, $( $index:expr ),+) => {{
-        #[inline(always)]
-        fn func<'a, 'b, T>(slice: &'a mut [T], indices: &'b mut [usize]) -> ($($crate::to_type!($index)),+) {
-            if slice.is_empty() {
-                panic!("Requested indices but slice is empty.")
-            }
-            $crate::insertion_sort(indices);
-
-            let indices_len_minus_one = indices.len() - 1;
-            let slice_len_minus_one = slice.len() - 1;
-            for i in 0..indices_len_minus_one {
-                if indices[i] == indices[i + 1] {
-                    panic!("Duplicate indices are not allowed. Index `{}` was requested twice.", indices[i])
-                }
-            }
-            if indices[indices_len_minus_one] > slice_len_minus_one {
-                panic!(
-                    "Index out of bounds. Requested index was `{}` while slice length was `{}`.",
-                    indices[indices_len_minus_one], slice_len_minus_one + 1
-                )
+    (try $check:path $(| $mixed:path)?; $slice:expr; [$(shared $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        match $check(&spans, claimer.len()) {
+            Ok(()) => {
+                let mut spans = spans.into_iter();
+                Ok(unsafe { ($(claimer.claim(&$request, $crate::next_of!(spans, $request)),)+) })
             }
-
-            let ptr = slice.as_mut_ptr();
-            (
-                $(unsafe { &mut *ptr.add($index) },)*
-            )
+            Err(error) => Err(error),
         }
-        let mut indices = [$($index),*];
-        func($slice, &mut indices)
-    }};
-}
-
-/// Returns mutable references for the requested indices.
-/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
-#[macro_export]
-macro_rules! try_indices {
-    ($slice:expr, $index1:expr) => {{
-        $slice.get_mut($index1).map(|e| (e,)).ok_or($crate::TryIndicesError::IndexOutOfBounds)
     }};
 
-    ($slice:expr, $index1:expr, $index2:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize) -> Result<(&mut T, &mut T), $crate::TryIndicesError> {
-            if one == two {
-                return Err($crate::TryIndicesError::DuplicateIndex);
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len {
-                return Err($crate::TryIndicesError::IndexOutOfBounds);
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                Ok((&mut *ptr.add(one), &mut *ptr.add(two)))
+    // Claims without an `unsafe` block, so the caller must provide one.
+    (unchecked $check:path $(| $mixed:path)?; $slice:expr; [$(shared $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        if cfg!(debug_assertions) {
+            if let Err(error) = $check(&spans, claimer.len()) {
+                panic!("{}", error)
             }
         }
-        func($slice, $index1, $index2)
+        let mut spans = spans.into_iter();
+        ($(claimer.claim(&$request, $crate::next_of!(spans, $request)),)+)
     }};
 
-    ($slice:expr, $index1:expr, $index2:expr, $index3:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize, three: usize) -> Result<(&mut T, &mut T, &mut T), $crate::TryIndicesError> {
-            if one == two || one == three || two == three {
-                return Err($crate::TryIndicesError::DuplicateIndex);
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len || three >= slice_len {
-                return Err($crate::TryIndicesError::IndexOutOfBounds);
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                Ok((&mut *ptr.add(one), &mut *ptr.add(two), &mut *ptr.add(three)))
+    (unchecked $check:path | $mixed:path; $slice:expr; [$($kind:tt $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        if cfg!(debug_assertions) {
+            let exclusive = [$($crate::claim_requests!(@exclusive $kind)),+];
+            if let Err(error) = $mixed(&spans, &exclusive, claimer.len()) {
+                panic!("{}", error)
             }
         }
-        func($slice, $index1, $index2, $index3)
+        let mut spans = spans.into_iter();
+        ($($crate::claim_requests!(@claim claimer spans $kind $request),)+)
     }};
 
-    ($slice:expr, $index1:expr, $index2:expr, $index3:expr, $index4:expr) => {{
-        #[inline(always)]
-        fn func<T>(slice: &mut [T], one: usize, two: usize, three: usize, four: usize) -> Result<(&mut T, &mut T, &mut T, &mut T), $crate::TryIndicesError> {
-            if one == two || one == three || one == four || two == three || two == four || three == four {
-                return Err($crate::TryIndicesError::DuplicateIndex);
-            }
-            let slice_len = slice.len();
-            if one >= slice_len || two >= slice_len || three >= slice_len || four >= slice_len {
-                return Err($crate::TryIndicesError::IndexOutOfBounds);
-            }
-            let ptr = slice.as_mut_ptr();
-            unsafe {
-                Ok((&mut *ptr.add(one), &mut *ptr.add(two), &mut *ptr.add(three), &mut *ptr.add(four)))
-            }
+    (panic $check:path | $mixed:path; $slice:expr; [$($kind:tt $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        let exclusive = [$($crate::claim_requests!(@exclusive $kind)),+];
+        if let Err(error) = $mixed(&spans, &exclusive, claimer.len()) {
+            panic!("{}", error)
         }
-        func($slice, $index1, $index2, $index3, $index4)
+        let mut spans = spans.into_iter();
+        unsafe { ($($crate::claim_requests!(@claim claimer spans $kind $request),)+) }
     }};
 
-    ($slice:expr, $( $index:expr ),+) => {{
-        #[inline(always)]
-        fn func<'a, 'b, T>(slice: &'a mut [T], indices: &'b mut [usize]) -> Result<($($crate::to_type!($index)),+), $crate::TryIndicesError> {
-            if slice.is_empty() {
-                return Err($crate::TryIndicesError::IndexOutOfBounds);
-            }
-            $crate::insertion_sort(indices);
-
-            let indices_len_minus_one = indices.len() - 1;
-            let slice_len_minus_one = slice.len() - 1;
-            for i in 0..indices_len_minus_one {
-                if indices[i] == indices[i + 1] {
-                    return Err($crate::TryIndicesError::DuplicateIndex);
-                }
-            }
-            if indices[indices_len_minus_one] > slice_len_minus_one {
-                return Err($crate::TryIndicesError::IndexOutOfBounds);
+    (try $check:path | $mixed:path; $slice:expr; [$($kind:tt $request:ident)+];) => {{
+        let claimer = $crate::Claimer::new($slice);
+        let spans = [$($crate::IndexRequest::span(&$request, claimer.len())),+];
+        let exclusive = [$($crate::claim_requests!(@exclusive $kind)),+];
+        match $mixed(&spans, &exclusive, claimer.len()) {
+            Ok(()) => {
+                let mut spans = spans.into_iter();
+                Ok(unsafe { ($($crate::claim_requests!(@claim claimer spans $kind $request),)+) })
             }
-
-            let ptr = slice.as_mut_ptr();
-            Ok((
-                $(unsafe { &mut *ptr.add($index) },)*
-            ))
+            Err(error) => Err(error),
         }
-        let mut indices = [$($index),*];
-        func($slice, &mut indices)
     }};
+
+    ($mode:ident $check:path; $slice:expr; [$($kind:tt $request:ident)+];) => {
+        compile_error!("`mut` requests are only supported by `indices!`, `try_indices!` and `indices_unchecked!`")
+    };
 }
 
-/// Returns mutable references for the requested indices
-/// Slightly more efficient than `indices!` since assumes the requested indices are already ordered smallest to largest.
-/// Panics if the requested indices are not smallest to largest, or if any index is duplicated or out of bounds.
+/// Returns mutable references for the requested indices and ranges. Each index is returned as
+/// `&mut T` and each range as `&mut [T]`. Indices may be of any `IndexType`. Untyped integers,
+/// such as unsuffixed literals and unannotated loop variables, are `usize`.
+///
+/// Once any request is marked `mut`, e.g. `indices!(slice, mut 3, 1, 1, 5)`, the unmarked requests
+/// are returned as `&T` and `&[T]` and may overlap each other, but not a `mut` request.
+/// Panics if any index or range is out of bounds, or if any two requests overlap.
 #[macro_export]
-macro_rules! indices_ordered {
-    ($slice:expr, $index1:expr) => {{
-        (&mut $slice[$index1],)
-    }};
-
-    ($slice:expr, $( $index:expr ),+) => {{
-        #[inline(always)]
-        fn func<'a, 'b, T>(slice: &'a mut [T], indices: &'b [usize]) -> ($($crate::to_type!($index)),+) {
-            if slice.is_empty() {
-                panic!("Requested indices but slice is empty.");
-            }
-
-            let indices_len_minus_one = indices.len() - 1;
-            let slice_len_minus_one = slice.len() - 1;
+macro_rules! indices {
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(panic $crate::check_requests | $crate::check_requests_mixed; $slice; []; $($index)+)
+    };
+}
 
-            for i in 0..indices_len_minus_one {
-                if indices[i] > slice_len_minus_one {
-                    panic!("Index out of bounds. Requested index was `{}` while slice length was `{}`.", indices[i], slice_len_minus_one + 1);
-                }
-                if indices[i] >= indices[i + 1] {
-                    panic!("Indices not sorted or duplicate indices detected.");
-                }
-            }
-            if indices[indices_len_minus_one] > slice_len_minus_one {
-                panic!("Index out of bounds. Requested index was `{}` while slice length was `{}`.", indices[indices_len_minus_one], slice_len_minus_one + 1);
-            }
+/// Returns mutable references for the requested indices and ranges. Each index is returned as
+/// `&mut T` and each range as `&mut [T]`. Requests may be marked `mut` like for `indices!`.
+/// Returns `TryIndicesError` if any index or range is out of bounds, or if any two requests overlap.
+#[macro_export]
+macro_rules! try_indices {
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(try $crate::check_requests | $crate::check_requests_mixed; $slice; []; $($index)+)
+    };
+}
 
-            let ptr = slice.as_mut_ptr();
-            (
-                $(unsafe { &mut *ptr.add($index) },)*
-            )
-        }
-        let indices = [$($index),*];
-        func($slice, &indices)
-    }};
+/// Returns mutable references for the requested indices and ranges.
+/// Slightly more efficient than `indices!` since assumes the requests are already ordered smallest to largest.
+/// Panics if the requests are not smallest to largest, if any index or range is out of bounds, or if any two
+/// requests overlap.
+#[macro_export]
+macro_rules! indices_ordered {
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(panic $crate::check_requests_ordered; $slice; []; $($index)+)
+    };
 }
 
-/// Returns mutable references for the requested indices.
-/// Slightly more efficient than `try_indices!` since assumes the requested indices are already ordered smallest to largest.
-/// Returns `TryOrderedIndicesError` if the requested indices are not smallest to largest, or if any index is duplicated or out of bounds.
+/// Returns mutable references for the requested indices and ranges.
+/// Slightly more efficient than `try_indices!` since assumes the requests are already ordered smallest to largest.
+/// Returns `TryIndicesOrderedError` if the requests are not smallest to largest, if any index or range is out of
+/// bounds, or if any two requests overlap.
 #[macro_export]
 macro_rules! try_indices_ordered {
-    ($slice:expr, $index1:expr) => {{
-        $slice.get_mut($index1).map(|e| (e,)).ok_or($crate::TryIndicesOrderedError::IndexOutOfBounds)
-    }};
-
-    ($slice:expr, $( $index:expr ),+) => {{
-        #[inline(always)]
-        fn func<'a, 'b, T>(slice: &'a mut [T], indices: &'b [usize]) -> Result<($($crate::to_type!($index)),+), $crate::TryIndicesOrderedError> {
-            if slice.is_empty() {
-                return Err($crate::TryIndicesOrderedError::IndexOutOfBounds);
-            }
-
-            let indices_len_minus_one = indices.len() - 1;
-            let slice_len_minus_one = slice.len() - 1;
-
-            for i in 0..indices_len_minus_one {
-                if indices[i] > slice_len_minus_one {
-                    return Err($crate::TryIndicesOrderedError::IndexOutOfBounds);
-                }
-                if indices[i] >= indices[i + 1] {
-                    return Err($crate::TryIndicesOrderedError::InvalidIndex);
-                }
-            }
-            if indices[indices_len_minus_one] > slice_len_minus_one {
-                return Err($crate::TryIndicesOrderedError::IndexOutOfBounds);
-            }
-
-            let ptr = slice.as_mut_ptr();
-            Ok((
-                $(unsafe { &mut *ptr.add($index) },)*
-            ))
-        }
-        let indices = [$($index),*];
-        func($slice, &indices)
-    }};
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(try $crate::check_requests_ordered; $slice; []; $($index)+)
+    };
 }
 
-//************************************************************************//
+/// Returns mutable references for the requested indices and ranges, without checking them in
+/// release builds. Must be called in an `unsafe` block. Requests may be marked `mut` like for
+/// `indices!`.
+///
+/// # Safety
+/// Every request must be in bounds, and no two requests may overlap. This is checked with a debug
+/// assertion.
+#[macro_export]
+macro_rules! indices_unchecked {
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(unchecked $crate::check_requests | $crate::check_requests_mixed; $slice; []; $($index)+)
+    };
+}
 
-#[doc(hidden)]
-pub fn insertion_sort<T: PartialOrd>(s: &mut [T]) {
-    for i in 1..s.len() {
-        let mut j = i;
-        while j > 0 && s[j - 1] > s[j] {
-            s.swap(j - 1, j);
-            j -= 1;
-        }
-    }
+/// Returns mutable references for the requested indices and ranges, without checking them in
+/// release builds. Must be called in an `unsafe` block.
+///
+/// # Safety
+/// The requests must be ordered smallest to largest, in bounds and may not overlap. This is checked
+/// with a debug assertion.
+#[macro_export]
+macro_rules! indices_ordered_unchecked {
+    ($slice:expr, $($index:tt)+) => {
+        $crate::claim_requests!(unchecked $crate::check_requests_ordered; $slice; []; $($index)+)
+    };
 }
 
 //************************************************************************//
@@ -479,14 +549,21 @@ pub fn insertion_sort<T: PartialOrd>(s: &mut [T]) {
 #[cfg(test)]
 mod tests {
     use crate::{
-        indices_array, indices_slice, indices_slices, TryIndicesError, TryIndicesOrderedError,
+        indices_array, indices_array_ordered_unchecked, indices_array_unchecked, try_indices_array,
+        TryIndicesError, TryIndicesOrderedError,
+    };
+    #[cfg(feature = "alloc")]
+    use crate::{
+        indices_slice, indices_slice_unchecked, indices_slices, indices_slices_unchecked,
+        indices_slices_vec, try_indices_slice, try_indices_slices, try_indices_slices_vec,
     };
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_works() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two] = indices_slice(slice, &mut [1, 3]).try_into().unwrap();
+        let [one, two] = indices_slice(slice, &[1, 3]).try_into().unwrap();
         assert_eq!(one, &mut 4);
         assert_eq!(two, &mut 2);
         *one = 10;
@@ -494,11 +571,12 @@ mod tests {
         assert_eq!(data, [5, 10, 3, 20, 1]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_out_of_order() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two] = indices_slice(slice, &mut [3, 1]).try_into().unwrap();
+        let [one, two] = indices_slice(slice, &[3, 1]).try_into().unwrap();
         assert_eq!(one, &mut 2);
         assert_eq!(two, &mut 4);
         *one = 10;
@@ -506,11 +584,12 @@ mod tests {
         assert_eq!(data, [5, 20, 3, 10, 1]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_more_than_two_indices() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two, three] = indices_slice(slice, &mut [3, 1, 2]).try_into().unwrap();
+        let [one, two, three] = indices_slice(slice, &[3, 1, 2]).try_into().unwrap();
         assert_eq!(one, &mut 2);
         assert_eq!(two, &mut 4);
         assert_eq!(three, &mut 3);
@@ -521,42 +600,145 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_duplicate_indices() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let _result = indices_slice(slice, &mut [3, 3]);
+        let _result = indices_slice(slice, &[3, 3]);
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_out_of_bounds() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let _result = indices_slice(slice, &mut [3, 5]);
+        let _result = indices_slice(slice, &[3, 5]);
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_empty_requested_indices() {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
-        let _result = indices_slice(slice, &mut [3]);
+        let _result = indices_slice(slice, &[3]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_empty_requested_empty() {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
-        let result = indices_slice(slice, &mut []);
+        let result = indices_slice(slice, &[0; 0]);
         assert!(result.is_empty())
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slice_not_empty_slice_requested_empty() {
         let mut data: [i32; 1] = [1];
         let slice = data.as_mut_slice();
-        let result = indices_slice(slice, &mut []);
+        let result = indices_slice(slice, &[0; 0]);
+        assert!(result.is_empty())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn indices_slice_many_indices() {
+        let mut data: Vec<usize> = (0..10_000).collect();
+        let indices: Vec<usize> = (0..5_000).rev().map(|i| i * 2).collect();
+        let result = indices_slice(&mut data, &indices);
+        assert_eq!(result.len(), 5_000);
+        for (element, index) in result.into_iter().zip(&indices) {
+            assert_eq!(element, index);
+            *element = 0;
+        }
+        assert_eq!(data.iter().filter(|e| **e == 0).count(), 5_000);
+    }
+
+    //************************************************************************//
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_works() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let [one, two] = try_indices_slice(slice, &[3, 1])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(one, &mut 2);
+        assert_eq!(two, &mut 4);
+        *one = 10;
+        *two = 20;
+        assert_eq!(data, [5, 20, 3, 10, 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_duplicate_indices() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slice(slice, &[3, 1, 3]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_out_of_bounds() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slice(slice, &[3, 5]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_empty_requested_indices() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slice(slice, &[3]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 3,
+                position: 0,
+                len: 0
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_empty_requested_empty() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slice(slice, &[0; 0]).unwrap();
+        assert!(result.is_empty())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slice_not_empty_slice_requested_empty() {
+        let mut data: [i32; 1] = [1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slice(slice, &[0; 0]).unwrap();
         assert!(result.is_empty())
     }
 
@@ -566,7 +748,7 @@ mod tests {
     fn indices_array_works() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two] = indices_array(slice, &mut [1, 3]);
+        let [one, two] = indices_array(slice, &[1, 3]);
         assert_eq!(one, &mut 4);
         assert_eq!(two, &mut 2);
         *one = 10;
@@ -578,7 +760,7 @@ mod tests {
     fn indices_array_out_of_order() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two] = indices_array(slice, &mut [3, 1]);
+        let [one, two] = indices_array(slice, &[3, 1]);
         assert_eq!(one, &mut 2);
         assert_eq!(two, &mut 4);
         *one = 10;
@@ -587,10 +769,66 @@ mod tests {
     }
 
     #[test]
-    fn indices_array_more_than_two_indices() {
+    fn indices_array_more_than_two_indices() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let [one, two, three] = indices_array(slice, &[3, 1, 2]);
+        assert_eq!(one, &mut 2);
+        assert_eq!(two, &mut 4);
+        assert_eq!(three, &mut 3);
+        *one = 10;
+        *two = 20;
+        *three = 30;
+        assert_eq!(data, [5, 20, 30, 10, 1]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn indices_array_duplicate_indices() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let [_one, _two] = indices_array(slice, &[3, 3]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn indices_array_out_of_bounds() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let [_one, _two] = indices_array(slice, &[3, 5]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn indices_array_empty_requested_indices() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let _result = indices_array(slice, &[3]);
+    }
+
+    #[test]
+    fn indices_array_empty_requested_empty() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let result = indices_array(slice, &[0; 0]);
+        assert!(result.is_empty())
+    }
+
+    #[test]
+    fn indices_array_not_empty_slice_requested_empty() {
+        let mut data: [i32; 1] = [1];
+        let slice = data.as_mut_slice();
+        let result = indices_array(slice, &[0; 0]);
+        assert!(result.is_empty())
+    }
+
+    //************************************************************************//
+
+    #[test]
+    fn try_indices_array_works() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [one, two, three] = indices_array(slice, &mut [3, 1, 2]);
+        let [one, two, three] = try_indices_array(slice, &[3, 1, 2]).unwrap();
         assert_eq!(one, &mut 2);
         assert_eq!(two, &mut 4);
         assert_eq!(three, &mut 3);
@@ -600,43 +838,64 @@ mod tests {
         assert_eq!(data, [5, 20, 30, 10, 1]);
     }
 
-    #[should_panic]
     #[test]
-    fn indices_array_duplicate_indices() {
+    fn try_indices_array_duplicate_indices() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [_one, _two] = indices_array(slice, &mut [3, 3]);
+        let result = try_indices_array(slice, &[3, 3]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 1
+            })
+        );
     }
 
-    #[should_panic]
     #[test]
-    fn indices_array_out_of_bounds() {
+    fn try_indices_array_out_of_bounds() {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
-        let [_one, _two] = indices_array(slice, &mut [3, 5]);
+        let result = try_indices_array(slice, &[3, 5]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
     }
 
-    #[should_panic]
     #[test]
-    fn indices_array_empty_requested_indices() {
+    fn try_indices_array_empty_requested_indices() {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
-        let _result = indices_array(slice, &mut [3]);
+        let result = try_indices_array(slice, &[3]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 3,
+                position: 0,
+                len: 0
+            })
+        );
     }
 
     #[test]
-    fn indices_array_empty_requested_empty() {
+    fn try_indices_array_empty_requested_empty() {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
-        let result = indices_array(slice, &mut []);
+        let result = try_indices_array(slice, &[0; 0]).unwrap();
         assert!(result.is_empty())
     }
 
     #[test]
-    fn indices_array_not_empty_slice_requested_empty() {
+    fn try_indices_array_not_empty_slice_requested_empty() {
         let mut data: [i32; 1] = [1];
         let slice = data.as_mut_slice();
-        let result = indices_array(slice, &mut []);
+        let result = try_indices_array(slice, &[0; 0]).unwrap();
         assert!(result.is_empty())
     }
 
@@ -804,6 +1063,36 @@ mod tests {
         let (_one, _two, _three, _four, _five) = indices!(slice, 1, 2, 3, 4, 3);
     }
 
+    #[should_panic(
+        expected = "Index out of bounds. Requested index was `5` at position `1` while slice length was `5`."
+    )]
+    #[test]
+    fn indices_2_out_of_bounds_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two) = indices!(slice, 3, 5);
+    }
+
+    #[should_panic(
+        expected = "Duplicate indices are not allowed. Index `3` was requested at positions `1` and `3`."
+    )]
+    #[test]
+    fn indices_4_duplicate_indices_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two, _three, _four) = indices!(slice, 1, 3, 4, 3);
+    }
+
+    #[should_panic(
+        expected = "Index out of bounds. Requested index was `7` at position `3` while slice length was `5`."
+    )]
+    #[test]
+    fn indices_5_out_of_bounds_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two, _three, _four, _five) = indices!(slice, 1, 2, 3, 7, 0);
+    }
+
     #[test]
     fn indices_can_return_mut_from_scope() {
         let mut data = [0, 1, 2, 3, 4];
@@ -826,7 +1115,14 @@ mod tests {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 3);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 3,
+                position: 0,
+                len: 0
+            })
+        )
     }
 
     #[test]
@@ -844,7 +1140,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 5);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 0,
+                len: 5
+            })
+        )
     }
 
     #[test]
@@ -876,7 +1179,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 3, 3);
-        assert_eq!(result, Err(TryIndicesError::DuplicateIndex))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 1
+            })
+        )
     }
 
     #[test]
@@ -884,7 +1194,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 3, 5);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        )
     }
 
     #[test]
@@ -906,7 +1223,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 3, 5);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 2,
+                len: 5
+            })
+        )
     }
 
     #[test]
@@ -914,7 +1238,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 3, 3);
-        assert_eq!(result, Err(TryIndicesError::DuplicateIndex))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 1,
+                second: 2
+            })
+        )
     }
 
     #[test]
@@ -938,7 +1269,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 3, 4, 5);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 3,
+                len: 5
+            })
+        )
     }
 
     #[test]
@@ -946,7 +1284,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 3, 4, 3);
-        assert_eq!(result, Err(TryIndicesError::DuplicateIndex))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 1,
+                second: 3
+            })
+        )
     }
 
     #[test]
@@ -972,7 +1317,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 2, 3, 4, 5);
-        assert_eq!(result, Err(TryIndicesError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 4,
+                len: 5
+            })
+        )
     }
 
     #[test]
@@ -980,7 +1332,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices!(slice, 1, 2, 3, 4, 3);
-        assert_eq!(result, Err(TryIndicesError::DuplicateIndex))
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 2,
+                second: 4
+            })
+        )
     }
 
     #[test]
@@ -1042,6 +1401,16 @@ mod tests {
         let (_one, _two) = indices_ordered!(slice, 3, 1);
     }
 
+    #[should_panic(
+        expected = "Indices not sorted. Requested index `1` at position `2` is smaller than the previous index `3`."
+    )]
+    #[test]
+    fn indices_ordered_out_of_order_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two, _three) = indices_ordered!(slice, 0, 3, 1);
+    }
+
     #[should_panic]
     #[test]
     fn indices_ordered_out_of_bounds() {
@@ -1051,6 +1420,7 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_ordered_empty_requested_indices() {
         let mut data: [i32; 0] = [];
@@ -1106,7 +1476,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices_ordered!(slice, 3, 3);
-        assert_eq!(result, Err(TryIndicesOrderedError::InvalidIndex));
+        assert_eq!(
+            result,
+            Err(TryIndicesOrderedError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 1
+            })
+        );
     }
 
     #[test]
@@ -1114,7 +1491,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices_ordered!(slice, 3, 1);
-        assert_eq!(result, Err(TryIndicesOrderedError::InvalidIndex));
+        assert_eq!(
+            result,
+            Err(TryIndicesOrderedError::NotSorted {
+                index: 1,
+                position: 1,
+                previous: 3
+            })
+        );
     }
 
     #[test]
@@ -1122,7 +1506,14 @@ mod tests {
         let mut data = [5, 4, 3, 2, 1];
         let slice = data.as_mut_slice();
         let result = try_indices_ordered!(slice, 3, 5);
-        assert_eq!(result, Err(TryIndicesOrderedError::IndexOutOfBounds));
+        assert_eq!(
+            result,
+            Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
     }
 
     #[test]
@@ -1130,7 +1521,14 @@ mod tests {
         let mut data: [i32; 0] = [];
         let slice = data.as_mut_slice();
         let result = try_indices_ordered!(slice, 3);
-        assert_eq!(result, Err(TryIndicesOrderedError::IndexOutOfBounds))
+        assert_eq!(
+            result,
+            Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: 3,
+                position: 0,
+                len: 0
+            })
+        )
     }
 
     #[test]
@@ -1152,6 +1550,454 @@ mod tests {
 
     //************************************************************************//
 
+    #[test]
+    fn try_indices_ordered_out_of_bounds_before_out_of_order() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_ordered!(slice, 1, 6, 2);
+        assert_eq!(
+            result,
+            Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: 6,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    //************************************************************************//
+
+    #[test]
+    fn indices_ranges() {
+        let mut data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let slice = data.as_mut_slice();
+        let (head, five, tail) = indices!(slice, 0..3, 5, 7..);
+        assert_eq!((&*head, *five, &*tail), (&[0, 1, 2][..], 5, &[7, 8, 9][..]));
+        head.copy_from_slice(tail);
+        *five = 50;
+        assert_eq!(data, [7, 8, 9, 3, 4, 50, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn indices_all_range_types() {
+        let mut data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let slice = data.as_mut_slice();
+        let (a, b, c, d, e) = indices!(slice, 8.., 3..=4, ..1, 6..6, 5..=5);
+        assert_eq!(a, [8, 9]);
+        assert_eq!(b, [3, 4]);
+        assert_eq!(c, [0]);
+        assert!(d.is_empty());
+        assert_eq!(e, [5]);
+        let (all,) = indices!(slice, ..);
+        assert_eq!(all.len(), 10);
+        let (front, two) = indices!(slice, ..=1, 2);
+        assert_eq!((front.len(), *two), (2, 2));
+    }
+
+    #[test]
+    fn indices_evaluates_requests_once() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let mut next = 0;
+        let mut advance = || {
+            next += 1;
+            next
+        };
+        let (one, two, rest) = indices!(slice, advance(), advance(), advance()..);
+        assert_eq!((*one, *two, &*rest), (4, 3, &[2, 1][..]));
+    }
+
+    /// Returns `0` the first time and `1` every time after, so two of them only pass the check if
+    /// each is converted once.
+    #[derive(Clone, Copy)]
+    struct FirstZero<'a>(&'a core::cell::Cell<usize>);
+
+    impl crate::IndexType for FirstZero<'_> {
+        fn to_index(self) -> usize {
+            let calls = self.0.get();
+            self.0.set(calls + 1);
+            (calls != 0) as usize
+        }
+    }
+
+    #[test]
+    fn indices_untyped_integers() {
+        let mut data = [0, 1, 2, 3, 4];
+        for k in 0..3 {
+            let slice = data.as_mut_slice();
+            let (first, second) = indices!(slice, k, k + 1);
+            *first += *second;
+        }
+        let i = 1;
+        let slice = data.as_mut_slice();
+        let (one, four) = indices!(slice, i, 4);
+        core::mem::swap(one, four);
+        assert_eq!(data, [1, 4, 5, 3, 3]);
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 0, 3_000_000_000),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 3_000_000_000,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[test]
+    fn indices_suffixed_literals() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (one, two) = indices!(slice, 1u32, 2u8);
+        core::mem::swap(one, two);
+        let slice = data.as_mut_slice();
+        let (zero, four) = try_indices!(slice, mut 0u64, 4).unwrap();
+        *zero += *four;
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 0, 18_446_744_073_709_551_616u128),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: usize::MAX,
+                position: 1,
+                len: 5
+            })
+        );
+        assert_eq!(data, [6, 3, 4, 2, 1]);
+    }
+
+    #[test]
+    fn indices_converts_index_types_once() {
+        let mut data = [5, 4, 3, 2, 1];
+        let calls = core::cell::Cell::new(0);
+        let (zero, one) = indices!(&mut data, FirstZero(&calls), FirstZero(&calls));
+        assert_eq!((*zero, *one), (5, 4));
+        *zero = 0;
+        *one = 0;
+        assert_eq!(calls.get(), 2);
+        calls.set(0);
+        let (zero, one) =
+            try_indices!(&mut data, mut FirstZero(&calls), FirstZero(&calls)).unwrap();
+        assert_eq!((*zero, *one), (0, 0));
+        calls.set(0);
+        let (zero, one) =
+            unsafe { indices_unchecked!(&mut data, FirstZero(&calls), FirstZero(&calls)) };
+        assert!(!core::ptr::eq(zero, one));
+        assert_eq!(data, [0, 0, 3, 2, 1]);
+    }
+
+    #[test]
+    fn indices_mixed_shared_and_mut() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (three, one, one_again, five) = indices!(slice, mut 3, 1, 1, 4);
+        *three = *one + *one_again + *five;
+        assert_eq!(data, [5, 4, 3, 9, 1]);
+        let slice = data.as_mut_slice();
+        let (first, window, tail) = indices!(slice, mut 0, 1..4, 2..);
+        *first = window.iter().sum::<i32>() + tail[0];
+        assert_eq!(data, [19, 4, 3, 9, 1]);
+        let slice = data.as_mut_slice();
+        let (zero, two) = indices!(slice, mut 0, mut 2);
+        core::mem::swap(zero, two);
+        assert_eq!(data, [3, 4, 19, 9, 1]);
+    }
+
+    #[test]
+    fn try_indices_mixed_errors() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 1, mut 1),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 1
+            })
+        );
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 3, 3, 2, mut 2..4),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 2,
+                first: 2,
+                second: 3
+            })
+        );
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 0, 0, mut 5),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 2,
+                len: 5
+            })
+        );
+        let slice = data.as_mut_slice();
+        let (four, zero, four_again) = try_indices!(slice, 4, mut 0, 4).unwrap();
+        *zero = *four + *four_again;
+        assert_eq!(data, [2, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn indices_unchecked_macros() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (one, rest) = unsafe { indices_unchecked!(slice, 1, 3..) };
+        *one += rest[0];
+        let slice = data.as_mut_slice();
+        let (zero, two, two_again) = unsafe { indices_unchecked!(slice, mut 0, 2, 2) };
+        *zero = *two + *two_again;
+        let slice = data.as_mut_slice();
+        let (zero, four) = unsafe { indices_ordered_unchecked!(slice, 0, 4) };
+        core::mem::swap(zero, four);
+        assert_eq!(data, [1, 6, 3, 2, 6]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "Duplicate indices are not allowed. Index `1` was requested at positions `0` and `1`."
+    )]
+    #[test]
+    fn indices_unchecked_debug_assertion() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two) = unsafe { indices_unchecked!(slice, 1, 1) };
+    }
+
+    #[test]
+    fn unchecked_functions() {
+        let mut data = [5, 4, 3, 2, 1];
+        let [four, zero] = unsafe { indices_array_unchecked(&mut data, &[4, 0]) };
+        core::mem::swap(four, zero);
+        let [one, three] = unsafe { indices_array_ordered_unchecked(&mut data, &[1, 3]) };
+        core::mem::swap(one, three);
+        assert_eq!(data, [1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unchecked_vec_functions() {
+        let mut data = [5, 4, 3, 2, 1];
+        for element in unsafe { indices_slice_unchecked(&mut data, &[3, 1]) } {
+            *element = 0;
+        }
+        let [first, mut second] = unsafe { indices_slices_unchecked(&mut data, [&[0, 2], &[4]]) };
+        *second[0] = *first[0] + *first[1];
+        assert_eq!(data, [5, 0, 3, 0, 8]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "Index out of bounds. Requested index was `5` at position `1` while slice length was `5`."
+    )]
+    #[test]
+    fn indices_array_unchecked_debug_assertion() {
+        let mut data = [5, 4, 3, 2, 1];
+        let _elements = unsafe { indices_array_unchecked(&mut data, &[0, 5]) };
+    }
+
+    #[should_panic(
+        expected = "Duplicate indices are not allowed. Index `2` was requested at positions `0` and `2`."
+    )]
+    #[test]
+    fn indices_overlapping_ranges_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two, _three) = indices!(slice, 1..3, 4, 2..);
+    }
+
+    #[should_panic(
+        expected = "Invalid range. Requested range was `3..6` at position `1` while slice length was `5`."
+    )]
+    #[test]
+    fn indices_range_out_of_bounds_message() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (_one, _two) = indices!(slice, 0, 3..6);
+    }
+
+    #[test]
+    fn try_indices_ranges() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (one, rest) = try_indices!(slice, 4, ..4).unwrap();
+        rest[0] += *one;
+        assert_eq!(
+            try_indices!(slice, 0..2, 1),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 1
+            })
+        );
+        let (start, end) = (3, 2);
+        assert_eq!(
+            try_indices!(slice, 0, start..end),
+            Err(TryIndicesError::InvalidRange {
+                start: 3,
+                end: 2,
+                position: 1,
+                len: 5
+            })
+        );
+        assert_eq!(
+            try_indices!(slice, 9, 6..),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 9,
+                position: 0,
+                len: 5
+            })
+        );
+        let (empty, all) = try_indices!(slice, 2..2, ..).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(all, [6, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn try_indices_inclusive_ranges() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let mut exhausted = 1..=2;
+        exhausted.by_ref().for_each(drop);
+        let (empty, three) = try_indices!(slice, exhausted, 3).unwrap();
+        assert_eq!((empty.len(), *three), (0, 2));
+        let (start, end) = (4, 3);
+        let (_, empty) = try_indices!(slice, 0, start..=end).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(
+            try_indices!(slice, 0, start + 1..=end),
+            Err(TryIndicesError::InvalidRange {
+                start: 5,
+                end: 4,
+                position: 1,
+                len: 5
+            })
+        );
+        assert_eq!(
+            try_indices!(slice, 0, 1..=usize::MAX),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: usize::MAX,
+                position: 1,
+                len: 5
+            })
+        );
+        let mut units = [(); usize::MAX];
+        let units = units.as_mut_slice();
+        assert_eq!(
+            try_indices!(units, ..=usize::MAX),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: usize::MAX,
+                position: 0,
+                len: usize::MAX
+            })
+        );
+        assert_eq!(
+            try_indices_ordered!(units, 0, 1..=usize::MAX),
+            Err(TryIndicesOrderedError::IndexOutOfBounds {
+                index: usize::MAX,
+                position: 1,
+                len: usize::MAX
+            })
+        );
+        let (all,) = try_indices!(units, ..=usize::MAX - 1).unwrap();
+        assert_eq!(all.len(), usize::MAX);
+    }
+
+    #[test]
+    fn indices_ordered_ranges() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (head, two, tail) = indices_ordered!(slice, ..2, 2, 3..);
+        head.swap(0, 1);
+        tail.swap(0, 1);
+        *two = 0;
+        assert_eq!(data, [4, 5, 0, 1, 2]);
+    }
+
+    #[test]
+    fn try_indices_ordered_ranges() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices_ordered!(slice, 2.., 0),
+            Err(TryIndicesOrderedError::NotSorted {
+                index: 0,
+                position: 1,
+                previous: 2
+            })
+        );
+        assert_eq!(
+            try_indices_ordered!(slice, 0..4, 2..2, 3),
+            Err(TryIndicesOrderedError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 2
+            })
+        );
+        assert_eq!(
+            try_indices_ordered!(slice, 0, 1..=5),
+            Err(TryIndicesOrderedError::InvalidRange {
+                start: 1,
+                end: 6,
+                position: 1,
+                len: 5
+            })
+        );
+        let (empty, one, rest) = try_indices_ordered!(slice, 0..0, 0, 1..).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!((*one, rest.len()), (5, 4));
+    }
+
+    //************************************************************************//
+
+    #[test]
+    fn try_indices_error_display() {
+        let out_of_bounds = TryIndicesError::IndexOutOfBounds {
+            index: 7,
+            position: 2,
+            len: 4,
+        };
+        assert_eq!(
+            out_of_bounds.to_string(),
+            "Index out of bounds. Requested index was `7` at position `2` while slice length was `4`."
+        );
+        let duplicate = TryIndicesError::DuplicateIndex {
+            index: 1,
+            first: 0,
+            second: 3,
+        };
+        assert_eq!(
+            duplicate.to_string(),
+            "Duplicate indices are not allowed. Index `1` was requested at positions `0` and `3`."
+        );
+        let invalid_range = TryIndicesError::InvalidRange {
+            start: 2,
+            end: 9,
+            position: 1,
+            len: 4,
+        };
+        assert_eq!(
+            invalid_range.to_string(),
+            "Invalid range. Requested range was `2..9` at position `1` while slice length was `4`."
+        );
+    }
+
+    #[test]
+    fn try_indices_ordered_error_display() {
+        let not_sorted = TryIndicesOrderedError::NotSorted {
+            index: 2,
+            position: 1,
+            previous: 4,
+        };
+        assert_eq!(
+            not_sorted.to_string(),
+            "Indices not sorted. Requested index `2` at position `1` is smaller than the previous index `4`."
+        );
+    }
+
+    //************************************************************************//
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_works() {
         let mut data = [5, 4, 3, 2, 1];
@@ -1164,6 +2010,7 @@ mod tests {
         assert_eq!(data, [5, 10, 3, 2, 20]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_more_than_two_indices() {
         let mut data = [5, 4, 3, 2, 1];
@@ -1178,6 +2025,7 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_duplicate_indices() {
         let mut data = [5, 4, 3, 2, 1];
@@ -1186,6 +2034,7 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_duplicate_indices_different_slice() {
         let mut data = [5, 4, 3, 2, 1];
@@ -1194,6 +2043,7 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_out_of_bounds() {
         let mut data = [5, 4, 3, 2, 1];
@@ -1201,6 +2051,7 @@ mod tests {
         let [_one, _two] = indices_slices(slice, [&mut [3, 5], &mut [1, 0]]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_not_empty_and_indices_requested_has_indices() {
         let mut data: [i32; 1] = [1];
@@ -1211,6 +2062,7 @@ mod tests {
     }
 
     #[should_panic]
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_empty_and_indices_requested_has_indices() {
         let mut data: [i32; 0] = [];
@@ -1218,6 +2070,7 @@ mod tests {
         let _result = indices_slices(slice, [&mut [0]]);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_not_empty_and_indices_requested_has_no_indices() {
         let mut data: [i32; 1] = [1];
@@ -1227,6 +2080,7 @@ mod tests {
         assert!(result[0].is_empty())
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_empty_and_indices_requested_has_no_indices() {
         let mut data: [i32; 0] = [];
@@ -1236,6 +2090,7 @@ mod tests {
         assert!(result[0].is_empty())
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_not_empty_and_indices_requested_empty() {
         let mut data: [i32; 1] = [1];
@@ -1244,6 +2099,7 @@ mod tests {
         assert_eq!(result.len(), 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn indices_slices_data_empty_and_indices_requested_empty() {
         let mut data: [i32; 0] = [];
@@ -1251,10 +2107,165 @@ mod tests {
         let result = indices_slices(slice, []);
         assert_eq!(result.len(), 0);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn indices_slices_vec_runtime_groups() {
+        let mut data: Vec<usize> = (0..10).collect();
+        let workers = 3;
+        let groups: Vec<Vec<usize>> = (0..workers)
+            .map(|worker| (worker..10).step_by(workers).collect())
+            .collect();
+        let groups: Vec<&[usize]> = groups.iter().map(Vec::as_slice).collect();
+        let mut partition = indices_slices_vec(&mut data, &groups);
+        assert_eq!(partition.len(), 3);
+        for (worker, group) in partition.iter_mut().enumerate() {
+            for element in group {
+                **element = worker;
+            }
+        }
+        assert_eq!(data, [0, 1, 2, 0, 1, 2, 0, 1, 2, 0]);
+        assert!(indices_slices_vec(&mut data, &[]).is_empty());
+        assert_eq!(
+            try_indices_slices_vec(&mut data, &[&[], &[1, 9]]).map(|groups| groups.len()),
+            Ok(2)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_vec_errors() {
+        let mut data = [0, 1, 2, 3, 4];
+        assert_eq!(
+            try_indices_slices_vec(&mut data, &[&[3, 1], &[0], &[2, 1]]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 1,
+                second: 4
+            })
+        );
+        assert_eq!(
+            try_indices_slices_vec(&mut data, &[&[4], &[5]]),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[should_panic(
+        expected = "Duplicate indices are not allowed. Index `2` was requested at positions `0` and `2`."
+    )]
+    #[test]
+    fn indices_slices_vec_duplicate_message() {
+        let mut data = [0, 1, 2, 3, 4];
+        let _groups = indices_slices_vec(&mut data, &[&[2], &[0, 2]]);
+    }
+
+    //************************************************************************//
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_works() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let [mut one, mut two] = try_indices_slices(slice, [&[1, 3], &[4, 2]]).unwrap();
+        assert_eq!(one, [&mut 4, &mut 2]);
+        assert_eq!(two, [&mut 1, &mut 3]);
+        *one[0] = 10;
+        *two[0] = 20;
+        assert_eq!(data, [5, 10, 3, 2, 20]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_duplicate_indices() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, [&[3, 3], &[1, 2]]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 1
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_duplicate_indices_different_slice() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, [&[3, 1], &[2, 3]]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::DuplicateIndex {
+                index: 3,
+                first: 0,
+                second: 3
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_out_of_bounds() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, [&[3, 5], &[1, 0]]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_data_empty_and_indices_requested_has_indices() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, [&[0]]);
+        assert_eq!(
+            result,
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 0,
+                position: 0,
+                len: 0
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_data_empty_and_indices_requested_has_no_indices() {
+        let mut data: [i32; 0] = [];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, [&[]]).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_empty())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_indices_slices_data_not_empty_and_indices_requested_empty() {
+        let mut data: [i32; 1] = [1];
+        let slice = data.as_mut_slice();
+        let result = try_indices_slices(slice, []).unwrap();
+        assert_eq!(result.len(), 0);
+    }
 }
 
 #[cfg(test)]
 mod example_tests {
+    #[cfg(feature = "alloc")]
     use crate::indices_slices;
 
     #[test]
@@ -1298,6 +2309,7 @@ mod example_tests {
         assert_eq!(data[3].last, "Jones");
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn graph_example() {
         struct Node {
@@ -1352,9 +2364,9 @@ mod example_tests {
                     return true;
                 }
             }
-            return false;
+            false
         }
-        traverse_graph(&mut *graph, 2, 0);
+        traverse_graph(&mut graph, 2, 0);
         let answers = [
             "This is Node `0` Came from Node `1`.",
             "This is Node `1` Came from Node `3`.",
@@ -0,0 +1,240 @@
+use crate::TryIndicesMapError;
+use alloc::collections::BTreeMap;
+use core::{borrow::Borrow, ops::Bound};
+#[cfg(feature = "std")]
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+/// A map whose values can be retrieved mutably by key with `indices_map!` and `indices_map`.
+/// Implemented for `HashMap` (with the `std` feature) and `BTreeMap`.
+pub trait IndicesMap<Q: ?Sized> {
+    /// The type of the values in the map.
+    type Value;
+
+    /// Returns mutable references to the values of `keys`, or `None` for keys missing from the map.
+    /// `keys` must not contain duplicates.
+    #[doc(hidden)]
+    fn get_distinct_mut<const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut Self::Value>; N];
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, Q> IndicesMap<Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    #[inline]
+    fn get_distinct_mut<const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut V>; N] {
+        self.get_disjoint_mut(keys)
+    }
+}
+
+/// Visits the values between the smallest and largest requested key once, in order. This takes time
+/// proportional to the number of entries in that range, up to the size of the map, since a
+/// `BTreeMap` cannot hand out mutable references to separately looked up values.
+impl<K, V, Q> IndicesMap<Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Value = V;
+
+    fn get_distinct_mut<const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut V>; N] {
+        let mut result: [Option<&mut V>; N] = core::array::from_fn(|_| None);
+        if N == 0 {
+            return result;
+        }
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by(|a, b| keys[*a].cmp(keys[*b]));
+        let range = (
+            Bound::Included(keys[order[0]]),
+            Bound::Included(keys[order[N - 1]]),
+        );
+        let mut next = 0;
+        for (key, value) in self.range_mut::<Q, _>(range) {
+            let key = key.borrow();
+            while keys[order[next]] < key {
+                next += 1;
+            }
+            if keys[order[next]] == key {
+                result[order[next]] = Some(value);
+                next += 1;
+                if next == N {
+                    break;
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Returns mutable references to the values of the requested keys.
+/// Panics if any key is missing or duplicated. See `try_indices_map` for the cost on a `BTreeMap`.
+pub fn indices_map<'a, M, Q, const N: usize>(map: &'a mut M, keys: [&Q; N]) -> [&'a mut M::Value; N]
+where
+    M: IndicesMap<Q> + ?Sized,
+    Q: Eq + ?Sized,
+{
+    match try_indices_map(map, keys) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Returns mutable references to the values of the requested keys.
+/// Returns `TryIndicesMapError` if any key is missing or duplicated.
+///
+/// A `HashMap` looks up each key separately. A `BTreeMap` visits every entry between the smallest
+/// and largest requested key, which is O(map size) for keys far apart.
+pub fn try_indices_map<'a, M, Q, const N: usize>(
+    map: &'a mut M,
+    keys: [&Q; N],
+) -> Result<[&'a mut M::Value; N], TryIndicesMapError>
+where
+    M: IndicesMap<Q> + ?Sized,
+    Q: Eq + ?Sized,
+{
+    for second in 1..N {
+        if let Some(first) = keys[..second].iter().position(|key| *key == keys[second]) {
+            return Err(TryIndicesMapError::DuplicateKey { first, second });
+        }
+    }
+    let values = map.get_distinct_mut(keys);
+    if let Some(position) = values.iter().position(Option::is_none) {
+        return Err(TryIndicesMapError::MissingKey { position });
+    }
+    Ok(values.map(Option::unwrap))
+}
+
+/// Returns mutable references to the values of the requested keys of a `HashMap` or `BTreeMap`.
+/// Panics if any key is missing or duplicated.
+#[macro_export]
+macro_rules! indices_map {
+    ($map:expr, $( $key:expr ),+) => {{
+        let mut iter = $crate::indices_map($map, [$($key),+]).into_iter();
+        ($($crate::next_of!(iter, $key),)+)
+    }};
+}
+
+/// Returns mutable references to the values of the requested keys of a `HashMap` or `BTreeMap`.
+/// Returns `TryIndicesMapError` if any key is missing or duplicated.
+#[macro_export]
+macro_rules! try_indices_map {
+    ($map:expr, $( $key:expr ),+) => {{
+        $crate::try_indices_map($map, [$($key),+]).map(|array| {
+            let mut iter = array.into_iter();
+            ($($crate::next_of!(iter, $key),)+)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices_map, try_indices_map, TryIndicesMapError};
+    use std::collections::BTreeMap;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    fn accounts() -> [(String, i64); 5] {
+        [
+            ("alice".to_string(), 100),
+            ("bob".to_string(), 50),
+            ("carol".to_string(), 0),
+            ("dave".to_string(), 10),
+            ("erin".to_string(), 70),
+        ]
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_transfer() {
+        let mut map: HashMap<String, i64> = accounts().into_iter().collect();
+        let [from, to] = indices_map(&mut map, ["alice", "carol"]);
+        *from -= 30;
+        *to += 30;
+        assert_eq!(map["alice"], 70);
+        assert_eq!(map["carol"], 30);
+    }
+
+    #[test]
+    fn btree_map_out_of_order_keys() {
+        let mut map: BTreeMap<String, i64> = accounts().into_iter().collect();
+        let [erin, alice, dave] = indices_map(&mut map, ["erin", "alice", "dave"]);
+        assert_eq!((*erin, *alice, *dave), (70, 100, 10));
+        *erin = 1;
+        *alice = 2;
+        *dave = 3;
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [2, 50, 0, 3, 1]);
+    }
+
+    #[cfg(feature = "std")]
+    #[should_panic]
+    #[test]
+    fn hash_map_missing_key() {
+        let mut map: HashMap<String, i64> = accounts().into_iter().collect();
+        let [_one, _two] = indices_map(&mut map, ["alice", "mallory"]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn try_indices_map_hash_map_errors() {
+        let mut map: HashMap<String, i64> = accounts().into_iter().collect();
+        assert_eq!(
+            try_indices_map(&mut map, ["bob", "carol", "bob"]),
+            Err(TryIndicesMapError::DuplicateKey {
+                first: 0,
+                second: 2
+            })
+        );
+        assert_eq!(
+            try_indices_map(&mut map, ["bob", "zed"]),
+            Err(TryIndicesMapError::MissingKey { position: 1 })
+        );
+    }
+
+    #[test]
+    fn try_indices_map_btree_map_errors() {
+        let mut map: BTreeMap<String, i64> = accounts().into_iter().collect();
+        assert_eq!(
+            try_indices_map(&mut map, ["bob", "carol", "bob"]),
+            Err(TryIndicesMapError::DuplicateKey {
+                first: 0,
+                second: 2
+            })
+        );
+        let missing = Err(TryIndicesMapError::MissingKey { position: 1 });
+        assert_eq!(try_indices_map(&mut map, ["bob", "aaron"]), missing);
+        assert_eq!(try_indices_map(&mut map, ["bob", "bobby"]), missing);
+        assert_eq!(try_indices_map(&mut map, ["erin", "zed"]), missing);
+    }
+
+    #[test]
+    fn indices_map_macro() {
+        let mut map: BTreeMap<u32, i64> = (0..10).map(|id| (id, 10)).collect();
+        let (three, seven) = indices_map!(&mut map, &3, &7);
+        *three += 5;
+        *seven -= 5;
+        assert_eq!((map[&3], map[&7]), (15, 5));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn try_indices_map_macro() {
+        let mut map: HashMap<u32, i64> = (0..10).map(|id| (id, 10)).collect();
+        assert_eq!(
+            try_indices_map!(&mut map, &1, &1),
+            Err(TryIndicesMapError::DuplicateKey {
+                first: 0,
+                second: 1
+            })
+        );
+        let (one,) = try_indices_map!(&mut map, &1).unwrap();
+        *one = 0;
+        assert_eq!(map[&1], 0);
+    }
+}
@@ -0,0 +1,342 @@
+//! Small inputs for every public function and macro arm, so `cargo miri test --lib miri_tests`
+//! checks the unsafe code without taking long. References are written to in an interleaved order
+//! after all of them are claimed, which Miri reports if any two of them alias.
+
+use crate::*;
+
+#[test]
+fn macros_with_indices() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let slice = data.as_mut_slice();
+    let (one, three) = indices!(slice, 1, 3);
+    *three += 10;
+    *one += 10;
+    *three += *one;
+    let slice = data.as_mut_slice();
+    let (five, zero) = try_indices!(slice, 5, 0).unwrap();
+    core::mem::swap(five, zero);
+    let slice = data.as_mut_slice();
+    let (two, four) = indices_ordered!(slice, 2, 4);
+    core::mem::swap(two, four);
+    let slice = data.as_mut_slice();
+    let (zero, one) = try_indices_ordered!(slice, 0, 1).unwrap();
+    core::mem::swap(zero, one);
+    assert_eq!(data, [11, 5, 4, 24, 2, 0]);
+}
+
+#[test]
+fn macros_with_expressions() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let (first, second) = (4, 1);
+    let slice = data.as_mut_slice();
+    let (four, one, five) = indices!(slice, first, second, first + 1);
+    *one += *four;
+    *five += *one;
+    *four = 0;
+    assert_eq!(data, [0, 5, 2, 3, 0, 10]);
+}
+
+#[test]
+fn macros_with_ranges() {
+    let mut data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let slice = data.as_mut_slice();
+    let (head, middle, inclusive, tail) = indices!(slice, ..2, 3..5, 6..=7, 9..);
+    middle[1] += head[0] + inclusive[1];
+    head[1] += tail[0];
+    inclusive[0] += middle[0];
+    let slice = data.as_mut_slice();
+    let (to_inclusive, index) = try_indices!(slice, ..=1, 8).unwrap();
+    *index += to_inclusive[1];
+    let slice = data.as_mut_slice();
+    let (all,) = indices!(slice, ..);
+    all[2] += all[0];
+    assert_eq!(data, [0, 10, 2, 3, 11, 5, 9, 7, 18, 9]);
+}
+
+#[test]
+fn macros_with_mut_requests() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let index = 4;
+    let slice = data.as_mut_slice();
+    let (sum, row, also_row, four) = indices!(slice, mut 0, 1..4, 2..=3, index);
+    *sum = row.iter().sum::<i32>() + also_row[0] + *four;
+    let slice = data.as_mut_slice();
+    let (five, four, zero) = try_indices!(slice, mut index + 1, index, 0).unwrap();
+    *five += *four + *zero;
+    assert_eq!(data, [12, 1, 2, 3, 4, 21]);
+}
+
+#[test]
+fn unchecked_macros() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let index = 5;
+    let slice = data.as_mut_slice();
+    let (one, rest) = unsafe { indices_unchecked!(slice, 1, 3..) };
+    *one += rest[2];
+    rest[0] += *one;
+    let slice = data.as_mut_slice();
+    let (zero, two, two_again) = unsafe { indices_unchecked!(slice, mut 0, 2, index - 3) };
+    *zero = *two + *two_again;
+    let slice = data.as_mut_slice();
+    let (zero, four) = unsafe { indices_ordered_unchecked!(slice, 0, 4) };
+    core::mem::swap(zero, four);
+    assert_eq!(data, [4, 6, 2, 9, 4, 5]);
+}
+
+#[test]
+fn array_functions() {
+    let mut data = [0, 1, 2, 3, 4];
+    let [four, one] = indices_array(&mut data, &[4, 1]);
+    *one += *four;
+    *four += *one;
+    let [zero, two] = try_indices_array_typed(&mut data, &[0u16, 2]).unwrap();
+    core::mem::swap(zero, two);
+    let [one, three] = indices_array_ordered(&mut data, &[1, 3]);
+    core::mem::swap(one, three);
+    let [three, four] = try_indices_array_ordered(&mut data, &[3, 4]).unwrap();
+    *three += *four;
+    let [] = indices_array(&mut data, &[]);
+    let [zero, four] = unsafe { indices_array_unchecked(&mut data, &[0, 4]) };
+    core::mem::swap(zero, four);
+    let [one, two] = unsafe { indices_array_ordered_unchecked(&mut data, &[1, 2]) };
+    core::mem::swap(one, two);
+    assert_eq!(data, [9, 0, 3, 14, 2]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_functions() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let mut elements = indices_slice(&mut data, &[5, 0, 3]);
+    *elements[1] += *elements[0];
+    *elements[2] += *elements[1];
+    *elements[0] = 0;
+    let mut elements = try_indices_slice_typed(&mut data, &[1u32, 2]).unwrap();
+    *elements[0] += *elements[1];
+    let [first, mut second] = indices_slices(&mut data, [&[0, 1], &[4]]);
+    *second[0] += *first[0] + *first[1];
+    let [first, second, mut third] = try_indices_slices(&mut data, [&[2], &[], &[3]]).unwrap();
+    assert!(second.is_empty());
+    *third[0] += *first[0];
+    let [] = indices_slices::<i32, 0>(&mut data, []);
+    assert!(indices_slice(&mut data, &[]).is_empty());
+    for element in unsafe { indices_slice_unchecked(&mut data, &[5, 3]) } {
+        *element += 1;
+    }
+    let [mut first, mut second] = unsafe { indices_slices_unchecked(&mut data, [&[1], &[0]]) };
+    core::mem::swap(first[0], second[0]);
+    let mut groups = indices_slices_vec(&mut data, &[&[2, 4], &[], &[1]]);
+    *groups[2][0] += *groups[0][1];
+    *groups[0][0] += *groups[2][0];
+    let mut groups = try_indices_slices_vec(&mut data, &[&[4], &[2]]).unwrap();
+    let (first, second) = groups.split_at_mut(1);
+    core::mem::swap(first[0][0], second[0][0]);
+    assert_eq!(data, [3, 17, 12, 11, 19, 1]);
+}
+
+#[test]
+fn zero_sized_elements() {
+    let mut data = [(); 4];
+    let [one, three] = indices_array(&mut data, &[1, 3]);
+    *one = *three;
+    let slice = data.as_mut_slice();
+    let (zero, rest) = indices!(slice, 0, 1..);
+    *zero = rest[0];
+    #[cfg(feature = "alloc")]
+    assert_eq!(indices_slice(&mut data, &[2, 0]).len(), 2);
+}
+
+#[test]
+fn extension_trait_and_typed_slice() {
+    let mut data = [0, 1, 2, 3, 4];
+    let [four, zero] = data.get_indices_mut([4, 0]);
+    core::mem::swap(four, zero);
+    let [one, two] = data.try_get_indices_ordered_mut([1, 2]).unwrap();
+    core::mem::swap(one, two);
+    #[cfg(feature = "alloc")]
+    {
+        let [mut first, mut second] = data.get_indices_slices_mut([&[3], &[1]]);
+        core::mem::swap(first[0], second[0]);
+    }
+    let mut nodes: TypedSlice<u16, i32> = TypedSlice::new(&mut data);
+    let [three, zero] = nodes.get_indices_mut([3, 0]);
+    core::mem::swap(three, zero);
+    #[cfg(feature = "alloc")]
+    for node in nodes.get_indices_slice_mut(&[4, 0]) {
+        *node += 10;
+    }
+    #[cfg(feature = "alloc")]
+    assert_eq!(data, [12, 3, 1, 4, 10]);
+}
+
+#[test]
+fn disjoint_indices() {
+    let mut data = [0, 1, 2, 3];
+    let indices = DisjointIndices::new([3, 1]).unwrap();
+    let [three, one] = indices.apply(&mut data);
+    *three += *one;
+    *one += *three;
+    #[cfg(feature = "alloc")]
+    {
+        let indices = DisjointIndicesVec::new(alloc::vec![2, 0]).unwrap();
+        let mut elements = indices.apply(&mut data);
+        *elements[0] += 1;
+        *elements[1] += *elements[0];
+    }
+    #[cfg(feature = "alloc")]
+    assert_eq!(data, [3, 5, 3, 4]);
+}
+
+#[test]
+fn nd_stencil_combinations_and_edges() {
+    let mut grid = [0, 1, 2, 3, 4, 5];
+    let [corner, centre] = indices_nd(&mut grid, [2, 3], &[[1, 2], [0, 1]]);
+    *corner += *centre;
+    let (left, right) = indices_nd!(&mut grid, (2, 3), (0, 0), (0, 2));
+    core::mem::swap(left, right);
+    assert!(try_indices_nd(&mut grid, [2, 3], &[[0, 0], [0, 0]]).is_err());
+    let _ = try_indices_nd!(&mut grid, (6,), (5,), (4,)).unwrap();
+    let stencil = Stencil::grid(3, [[0, -1], [0, 0], [1, 0]], EdgePolicy::Wrap);
+    if let [Some(left), Some(centre), Some(below)] = stencil.apply(&mut grid, 0) {
+        *centre += *left + *below;
+    }
+    assert!(Stencil::line([-1, 0], EdgePolicy::Error)
+        .try_apply(&mut grid, 0)
+        .is_err());
+    for_each_combination_mut::<3, _>(&mut grid[..4], |[a, b, c]| {
+        *a += 1;
+        *c += *b;
+    });
+    pairs_mut(&mut grid[..3], core::mem::swap);
+    for_each_pair_mut(&mut grid, &[(0, 5), (5, 0)], SelfLoops::Skip, |a, b| {
+        *a += *b
+    });
+    try_for_each_pair_mut(&mut grid, &[(1, 1)], SelfLoops::Skip, |_, _| {}).unwrap();
+    for_each_pair_or_self_mut(&mut grid, &[(2, 3), (4, 4)], |a, b| *a += *b, |a| *a *= 2);
+    try_for_each_pair_or_self_mut(&mut grid, &[(3, 2)], |a, b| *b -= *a, |_| {}).unwrap();
+    assert_eq!(grid[5], 13);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn arena() {
+    let mut arena = Arena::new();
+    let one = arena.insert(1);
+    let two = arena.insert(2);
+    let [first, second] = arena.get_many_mut([two, one]);
+    *first += *second;
+    *second += *first;
+    let (first, second) = get_many_mut!(&mut arena, one, two);
+    core::mem::swap(first, second);
+    assert!(try_get_many_mut!(&mut arena, one, one).is_err());
+    assert_eq!(
+        arena.try_get_many_mut([one, two]).map(|[a, b]| *a + *b),
+        Ok(7)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn iter_vec_deque_and_map() {
+    use alloc::collections::{BTreeMap, VecDeque};
+
+    let mut data = [0, 1, 2, 3, 4];
+    let elements: Vec<&mut i32> = indices_iter(&mut data, [4, 1, 4, 2])
+        .skip_duplicates()
+        .map(Result::unwrap)
+        .collect();
+    for element in elements {
+        *element += 10;
+    }
+    let mut iter = indices_iter(&mut data, [0, 0]);
+    let zero = iter.next().unwrap().unwrap();
+    assert!(iter.next().unwrap().is_err());
+    *zero += 1;
+    assert_eq!(data, [1, 11, 12, 3, 14]);
+
+    let mut deque: VecDeque<i32> = (0..4).collect();
+    deque.rotate_left(2);
+    deque.push_front(9);
+    let [front, back] = indices_vec_deque(&mut deque, &[0, 4]);
+    core::mem::swap(front, back);
+    let (one, three) = indices_vec_deque!(&mut deque, 1, 3);
+    *one += *three;
+    let (two,) = try_indices_vec_deque!(&mut deque, 2).unwrap();
+    *two = 0;
+    assert_eq!(deque, [1, 2, 0, 0, 9]);
+
+    let mut map: BTreeMap<&str, i32> = [("a", 1), ("b", 2), ("c", 3)].into();
+    let [c, a] = indices_map(&mut map, [&"c", &"a"]);
+    *a += *c;
+    let (b, c) = indices_map!(&mut map, &"b", &"c");
+    core::mem::swap(b, c);
+    assert!(try_indices_map!(&mut map, &"a", &"d").is_err());
+    assert_eq!(try_indices_map(&mut map, [&"a"]).map(|[a]| *a), Ok(4));
+    #[cfg(feature = "std")]
+    {
+        let mut map: std::collections::HashMap<i32, i32> = [(1, 1), (2, 2)].into();
+        let [one, two] = indices_map(&mut map, [&1, &2]);
+        core::mem::swap(one, two);
+        assert_eq!(map[&1], 2);
+    }
+}
+
+#[test]
+fn scratch_space() {
+    let mut data = [0, 1, 2, 3, 4];
+    let mut scratch = [0; 6];
+    let mut elements = indices_slice_with(&mut data, &[4usize, 0, 2], &mut scratch);
+    let mut iter = elements.iter_mut();
+    let (four, two) = (iter.next().unwrap(), iter.next_back().unwrap());
+    *four += *two;
+    *two += 1;
+    *four += 1;
+    let mut iter = elements.into_iter();
+    let (four, zero) = (iter.next().unwrap(), iter.next().unwrap());
+    core::mem::swap(four, zero);
+    let [mut one, mut three] = indices_slices_with(&mut data, [&[1usize][..], &[3]], &mut scratch);
+    core::mem::swap(one.get_mut(0).unwrap(), three.get_mut(0).unwrap());
+    assert_eq!(data, [7, 3, 3, 1, 0]);
+    #[cfg(feature = "alloc")]
+    {
+        let mut scratch = IndicesScratch::new();
+        for element in scratch.indices_slice(&mut data, &[1u8, 3]) {
+            *element += 10;
+        }
+        let [mut first, mut second] = scratch.indices_slices(&mut data, [&[0], &[4, 2]]);
+        core::mem::swap(first.get_mut(0).unwrap(), second.get_mut(1).unwrap());
+        assert_eq!(data, [3, 13, 7, 11, 0]);
+    }
+}
+
+#[test]
+fn split_arrays() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let (first, second, rest) = split_array_mut::<_, 2, 3, 6>(&mut data);
+    second[2] += rest[0] + first[1];
+    first[0] += second[0];
+    rest[0] = 0;
+    let (first, second) = sub_arrays_mut::<_, 4, 2, 0, 4, 6>(&mut data);
+    core::mem::swap(&mut first[1], &mut second[3]);
+    assert_eq!(data, [2, 1, 2, 0, 10, 3]);
+    let mut units = [(); 3];
+    let (_, _, rest) = split_array_mut::<_, 1, 1, 3>(&mut units);
+    assert_eq!(rest.len(), 1);
+}
+
+#[test]
+fn const_indices() {
+    struct Ends;
+
+    impl ConstIndices<2> for Ends {
+        const INDICES: [usize; 2] = [3, 0];
+    }
+
+    let mut data = [0, 1, 2, 3];
+    let [last, first] = get_const_mut(&mut data, Ends);
+    *first += *last;
+    *last += *first;
+    let (one, two) = indices_const!(&mut data, 1, 2);
+    core::mem::swap(one, two);
+    assert_eq!(data, [3, 2, 1, 6]);
+}
@@ -0,0 +1,171 @@
+use crate::{try_indices_array, TryIndicesError, TryIndicesNdError};
+
+/// Returns mutable references for the requested coordinates in a row-major buffer of the given
+/// shape, e.g. `[rows, columns]` with coordinates `[row, column]`.
+/// Panics if the shape does not match the slice length, if any coordinate is out of bounds for its
+/// axis, or if any coordinates are duplicated.
+pub fn indices_nd<'a, T, const D: usize, const N: usize>(
+    slice: &'a mut [T],
+    shape: [usize; D],
+    coordinates: &[[usize; D]; N],
+) -> [&'a mut T; N] {
+    match try_indices_nd(slice, shape, coordinates) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Returns mutable references for the requested coordinates in a row-major buffer of the given
+/// shape, e.g. `[rows, columns]` with coordinates `[row, column]`.
+/// Returns `TryIndicesNdError` if the shape does not match the slice length, if any coordinate is
+/// out of bounds for its axis, or if any coordinates are duplicated. Bounds are checked before
+/// duplicates, since coordinates that are out of bounds on one axis may alias others.
+pub fn try_indices_nd<'a, T, const D: usize, const N: usize>(
+    slice: &'a mut [T],
+    shape: [usize; D],
+    coordinates: &[[usize; D]; N],
+) -> Result<[&'a mut T; N], TryIndicesNdError> {
+    let elements = shape
+        .iter()
+        .try_fold(1usize, |elements, size| elements.checked_mul(*size));
+    if elements != Some(slice.len()) {
+        return Err(TryIndicesNdError::ShapeMismatch {
+            elements: elements.unwrap_or(usize::MAX),
+            len: slice.len(),
+        });
+    }
+    let mut offsets = [0; N];
+    for (position, coordinate) in coordinates.iter().enumerate() {
+        let mut offset = 0;
+        for axis in 0..D {
+            if coordinate[axis] >= shape[axis] {
+                return Err(TryIndicesNdError::CoordinateOutOfBounds {
+                    coordinate: coordinate[axis],
+                    axis,
+                    position,
+                    size: shape[axis],
+                });
+            }
+            offset = offset * shape[axis] + coordinate[axis];
+        }
+        offsets[position] = offset;
+    }
+    match try_indices_array(slice, &offsets) {
+        Ok(result) => Ok(result),
+        Err(TryIndicesError::DuplicateIndex { first, second, .. }) => {
+            Err(TryIndicesNdError::DuplicateCoordinates { first, second })
+        }
+        Err(error) => unreachable!("Offsets were checked against the shape: {}", error),
+    }
+}
+
+/// Returns mutable references for the requested coordinates in a row-major buffer of the given shape,
+/// e.g. `indices_nd!(slice, (rows, columns), (0, 1), (2, 3))`.
+/// Panics if the shape does not match the slice length, if any coordinate is out of bounds for its axis,
+/// or if any coordinates are duplicated.
+#[macro_export]
+macro_rules! indices_nd {
+    ($slice:expr, ($( $size:expr ),* $(,)?), $( ($( $coordinate:expr ),* $(,)?) ),+) => {{
+        let mut iter = $crate::indices_nd($slice, [$($size),*], &[$([$($coordinate),*]),+]).into_iter();
+        ($($crate::next_of!(iter, [$($coordinate),*]),)+)
+    }};
+}
+
+/// Returns mutable references for the requested coordinates in a row-major buffer of the given shape,
+/// e.g. `try_indices_nd!(slice, (rows, columns), (0, 1), (2, 3))`.
+/// Returns `TryIndicesNdError` if the shape does not match the slice length, if any coordinate is out of
+/// bounds for its axis, or if any coordinates are duplicated.
+#[macro_export]
+macro_rules! try_indices_nd {
+    ($slice:expr, ($( $size:expr ),* $(,)?), $( ($( $coordinate:expr ),* $(,)?) ),+) => {{
+        $crate::try_indices_nd($slice, [$($size),*], &[$([$($coordinate),*]),+]).map(|array| {
+            let mut iter = array.into_iter();
+            ($($crate::next_of!(iter, [$($coordinate),*]),)+)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices_nd, try_indices_nd, TryIndicesNdError};
+
+    #[test]
+    fn indices_nd_2d() {
+        let mut grid = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+        let [top_left, middle, bottom_right] =
+            indices_nd(&mut grid, [3, 4], &[[0, 0], [1, 2], [2, 3]]);
+        assert_eq!((*top_left, *middle, *bottom_right), (0, 6, 11));
+        std::mem::swap(top_left, bottom_right);
+        *middle = 60;
+        assert_eq!(grid, [11, 1, 2, 3, 4, 5, 60, 7, 8, 9, 10, 0]);
+    }
+
+    #[test]
+    fn indices_nd_3d() {
+        let mut volume: [usize; 24] = core::array::from_fn(|i| i);
+        let [one, two] = indices_nd(&mut volume, [2, 3, 4], &[[1, 2, 3], [0, 1, 2]]);
+        assert_eq!((*one, *two), (23, 6));
+    }
+
+    #[test]
+    fn try_indices_nd_errors() {
+        let mut grid = [0; 12];
+        assert_eq!(
+            try_indices_nd(&mut grid, [3, 5], &[[0, 0]]),
+            Err(TryIndicesNdError::ShapeMismatch {
+                elements: 15,
+                len: 12
+            })
+        );
+        assert_eq!(
+            try_indices_nd(&mut grid, [usize::MAX, 2], &[[0, 0]]),
+            Err(TryIndicesNdError::ShapeMismatch {
+                elements: usize::MAX,
+                len: 12
+            })
+        );
+        // `[0, 5]` would alias `[1, 1]` in the flat buffer.
+        assert_eq!(
+            try_indices_nd(&mut grid, [3, 4], &[[1, 1], [0, 5]]),
+            Err(TryIndicesNdError::CoordinateOutOfBounds {
+                coordinate: 5,
+                axis: 1,
+                position: 1,
+                size: 4
+            })
+        );
+        assert_eq!(
+            try_indices_nd(&mut grid, [3, 4], &[[2, 1], [0, 0], [2, 1]]),
+            Err(TryIndicesNdError::DuplicateCoordinates {
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[should_panic(
+        expected = "Coordinate out of bounds. Requested coordinate was `3` on axis `0` at position `0` while axis size was `3`."
+    )]
+    #[test]
+    fn indices_nd_out_of_bounds_message() {
+        let mut grid = [0; 12];
+        let [_one] = indices_nd(&mut grid, [3, 4], &[[3, 0]]);
+    }
+
+    #[test]
+    fn indices_nd_macros() {
+        let mut grid = [0, 1, 2, 3, 4, 5];
+        let (left, right) = indices_nd!(&mut grid, (2, 3), (1, 0), (0, 2));
+        *left += *right;
+        assert_eq!(grid, [0, 1, 2, 5, 4, 5]);
+        assert_eq!(
+            try_indices_nd!(&mut grid, (2, 3), (1, 1), (1, 1)),
+            Err(TryIndicesNdError::DuplicateCoordinates {
+                first: 0,
+                second: 1
+            })
+        );
+        let (one,) = try_indices_nd!(&mut grid, (2, 3), (0, 1)).unwrap();
+        assert_eq!(*one, 1);
+    }
+}
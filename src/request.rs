@@ -0,0 +1,269 @@
+use core::marker::PhantomData;
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+/// A type that indexes a single element of a slice, such as an unsigned integer or a strongly typed
+/// id, e.g. `struct NodeId(u32)`.
+pub trait IndexType: Copy {
+    /// Returns the index into the slice. A value that does not fit in a `usize` must return
+    /// `usize::MAX`, which is out of bounds for every slice.
+    fn to_index(self) -> usize;
+}
+
+macro_rules! impl_index_type {
+    ($($int:ty),+) => {$(
+        impl IndexType for $int {
+            #[inline(always)]
+            fn to_index(self) -> usize {
+                usize::try_from(self).unwrap_or(usize::MAX)
+            }
+        }
+    )+};
+}
+
+impl_index_type!(u8, u16, u32, u64, u128, usize);
+
+/// Wraps an argument of the `indices!` family of macros. Untyped integers, including unsuffixed
+/// literals and unannotated variables, are inferred as `usize` by the inherent `request`, which
+/// method resolution prefers over [`GenericRequest::request`] used by every other request.
+#[doc(hidden)]
+pub struct RequestOf<R>(pub R);
+
+impl RequestOf<usize> {
+    #[inline(always)]
+    pub fn request(self) -> usize {
+        self.0
+    }
+}
+
+#[doc(hidden)]
+pub trait GenericRequest {
+    type Request: IndexRequest;
+
+    fn request(self) -> Self::Request;
+}
+
+impl<R: IndexRequest> GenericRequest for RequestOf<R> {
+    type Request = R;
+
+    #[inline(always)]
+    fn request(self) -> R {
+        self.0
+    }
+}
+
+/// An argument of the `indices!` family of macros. An `IndexType` requests a single element as
+/// `&mut T`, any range of `usize` requests a subslice as `&mut [T]`. Shared requests return `&T`
+/// and `&[T]` instead.
+///
+/// This trait is sealed. Each request is resolved to a [`Span`] once, and the references are
+/// claimed from that checked span, never from the request again.
+pub trait IndexRequest: sealed::Sealed {
+    /// The reference returned for this request.
+    type Output<'a, T: 'a>;
+
+    /// The reference returned for this request when it is shared with other requests.
+    type SharedOutput<'a, T: 'a>;
+
+    /// Resolves the request against a slice of length `len`. The span is not validated.
+    #[doc(hidden)]
+    fn span(&self, len: usize) -> Span;
+
+    /// # Safety
+    /// `span` must have been returned by `span` for this request, be in bounds for the elements at
+    /// `ptr` and must not overlap any other live reference into them.
+    #[doc(hidden)]
+    unsafe fn claim<'a, T: 'a>(span: Span, ptr: *mut T) -> Self::Output<'a, T>;
+
+    /// # Safety
+    /// `span` must have been returned by `span` for this request, be in bounds for the elements at
+    /// `ptr` and must not overlap any other live mutable reference into them.
+    #[doc(hidden)]
+    unsafe fn claim_shared<'a, T: 'a>(span: Span, ptr: *const T) -> Self::SharedOutput<'a, T>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<I: super::IndexType> Sealed for I {}
+}
+
+/// The elements covered by a request.
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Span {
+    Index(usize),
+    /// `start..end`. Decreasing ranges are kept as requested so they can be reported.
+    Range {
+        start: usize,
+        end: usize,
+    },
+    /// `start..=usize::MAX`, whose exclusive end does not fit in a `usize`. Never in bounds.
+    ToMax {
+        start: usize,
+    },
+}
+
+impl Span {
+    /// Resolves `start..=end` like std: an exhausted range is empty at `end + 1`, and an `end` of
+    /// `usize::MAX` is never in bounds.
+    #[inline]
+    fn inclusive(start: usize, end: usize, exhausted: bool) -> Span {
+        match end.checked_add(1) {
+            None => Span::ToMax { start },
+            Some(end) if exhausted => Span::Range { start: end, end },
+            Some(end) => Span::Range { start, end },
+        }
+    }
+
+    #[inline]
+    pub(crate) fn start(self) -> usize {
+        match self {
+            Span::Index(index) => index,
+            Span::Range { start, .. } | Span::ToMax { start } => start,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn end(self) -> usize {
+        match self {
+            Span::Index(index) => index.saturating_add(1),
+            Span::Range { end, .. } => end,
+            Span::ToMax { .. } => usize::MAX,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(self) -> bool {
+        match self {
+            Span::Index(_) | Span::ToMax { .. } => false,
+            Span::Range { start, end } => start >= end,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn contains(self, index: usize) -> bool {
+        match self {
+            Span::Index(requested) => requested == index,
+            Span::Range { start, end } => start <= index && index < end,
+            Span::ToMax { start } => start <= index,
+        }
+    }
+
+    /// Returns the smallest index covered by both spans.
+    #[inline]
+    pub(crate) fn overlap(self, other: Span) -> Option<usize> {
+        match (self, other) {
+            (Span::Index(one), Span::Index(two)) => (one == two).then_some(one),
+            _ => {
+                let start = self.start().max(other.start());
+                (!self.is_empty() && !other.is_empty() && start < self.end().min(other.end()))
+                    .then_some(start)
+            }
+        }
+    }
+}
+
+impl<I: IndexType> IndexRequest for I {
+    type Output<'a, T: 'a> = &'a mut T;
+    type SharedOutput<'a, T: 'a> = &'a T;
+
+    #[inline]
+    fn span(&self, _len: usize) -> Span {
+        Span::Index(self.to_index())
+    }
+
+    #[inline]
+    unsafe fn claim<'a, T: 'a>(span: Span, ptr: *mut T) -> &'a mut T {
+        &mut *ptr.add(span.start())
+    }
+
+    #[inline]
+    unsafe fn claim_shared<'a, T: 'a>(span: Span, ptr: *const T) -> &'a T {
+        &*ptr.add(span.start())
+    }
+}
+
+macro_rules! impl_range_request {
+    ($($range:ty => |$this:ident, $len:ident| $span:expr;)+) => {$(
+        impl sealed::Sealed for $range {}
+
+        impl IndexRequest for $range {
+            type Output<'a, T: 'a> = &'a mut [T];
+            type SharedOutput<'a, T: 'a> = &'a [T];
+
+            #[inline]
+            fn span(&self, $len: usize) -> Span {
+                let $this = self;
+                $span
+            }
+
+            #[inline]
+            unsafe fn claim<'a, T: 'a>(span: Span, ptr: *mut T) -> &'a mut [T] {
+                core::slice::from_raw_parts_mut(ptr.add(span.start()), span.end() - span.start())
+            }
+
+            #[inline]
+            unsafe fn claim_shared<'a, T: 'a>(span: Span, ptr: *const T) -> &'a [T] {
+                core::slice::from_raw_parts(ptr.add(span.start()), span.end() - span.start())
+            }
+        }
+    )+};
+}
+
+impl_range_request! {
+    Range<usize> => |this, _len| Span::Range { start: this.start, end: this.end };
+    RangeInclusive<usize> => |this, _len| {
+        Span::inclusive(*this.start(), *this.end(), this.is_empty() && this.start() <= this.end())
+    };
+    RangeFrom<usize> => |this, len| Span::Range { start: this.start, end: len };
+    RangeTo<usize> => |this, _len| Span::Range { start: 0, end: this.end };
+    RangeToInclusive<usize> => |this, _len| Span::inclusive(0, this.end, false);
+    RangeFull => |_this, len| Span::Range { start: 0, end: len };
+}
+
+/// Hands out the requested references into a slice, bound to the lifetime of the slice.
+#[doc(hidden)]
+pub struct Claimer<'a, T> {
+    ptr: *mut T,
+    len: usize,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> Claimer<'a, T> {
+    #[inline(always)]
+    pub fn new(slice: &'a mut [T]) -> Self {
+        Claimer {
+            ptr: slice.as_mut_ptr(),
+            len: slice.len(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Claims `span`, which `request` resolved to. The request is only used for its type.
+    ///
+    /// # Safety
+    /// The spans claimed from this claimer must have passed `check_requests` or
+    /// `check_requests_ordered`.
+    #[inline(always)]
+    pub unsafe fn claim<R: IndexRequest>(&self, _request: &R, span: Span) -> R::Output<'a, T> {
+        R::claim(span, self.ptr)
+    }
+
+    /// # Safety
+    /// The spans claimed from this claimer must have passed `check_requests_mixed`, with this
+    /// span not exclusive.
+    #[inline(always)]
+    pub unsafe fn claim_shared<R: IndexRequest>(
+        &self,
+        _request: &R,
+        span: Span,
+    ) -> R::SharedOutput<'a, T> {
+        R::claim_shared(span, self.ptr)
+    }
+}
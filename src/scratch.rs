@@ -0,0 +1,449 @@
+use crate::check::check_indices;
+use crate::{IndexType, TryIndicesError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{iter::FusedIterator, marker::PhantomData};
+
+/// Returns the mutable references for validated indices of a slice, without allocating.
+/// Panics if any index is out of bounds or duplicated, or if `scratch` is shorter than twice
+/// `indices`.
+#[inline]
+pub fn indices_slice_with<'a, T, I: IndexType>(
+    slice: &'a mut [T],
+    indices: &[I],
+    scratch: &'a mut [usize],
+) -> IndicesSliceMut<'a, T> {
+    match try_indices_slice_with(slice, indices, scratch) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Returns the mutable references for validated indices of a slice, without allocating. The first
+/// half of `scratch` holds the converted indices, and the second half a copy that is sorted when
+/// there are too many to compare pairwise.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
+/// Panics if `scratch` is shorter than twice `indices`.
+#[inline]
+pub fn try_indices_slice_with<'a, T, I: IndexType>(
+    slice: &'a mut [T],
+    indices: &[I],
+    scratch: &'a mut [usize],
+) -> Result<IndicesSliceMut<'a, T>, TryIndicesError> {
+    let [result] = try_indices_slices_with(slice, [indices], scratch)?;
+    Ok(result)
+}
+
+/// Returns the mutable references for each group of validated indices of a slice, without
+/// allocating.
+/// Panics if any index is out of bounds or duplicated across all groups, or if `scratch` is shorter
+/// than twice the indices of all groups.
+#[inline]
+pub fn indices_slices_with<'a, T, I: IndexType, const N: usize>(
+    slice: &'a mut [T],
+    indices: [&[I]; N],
+    scratch: &'a mut [usize],
+) -> [IndicesSliceMut<'a, T>; N] {
+    match try_indices_slices_with(slice, indices, scratch) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Returns the mutable references for each group of validated indices of a slice, without
+/// allocating. `scratch` is used like for `try_indices_slice_with`.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated. Positions reported in the
+/// error count across all the requested groups, in order.
+/// Panics if `scratch` is shorter than twice the indices of all groups.
+pub fn try_indices_slices_with<'a, T, I: IndexType, const N: usize>(
+    slice: &'a mut [T],
+    indices: [&[I]; N],
+    scratch: &'a mut [usize],
+) -> Result<[IndicesSliceMut<'a, T>; N], TryIndicesError> {
+    let len: usize = indices.iter().map(|group| group.len()).sum();
+    assert!(
+        len <= scratch.len() / 2,
+        "Scratch space of length `{}` is shorter than twice the `{}` requested indices.",
+        scratch.len(),
+        len
+    );
+    let (requested, check) = scratch.split_at_mut(len);
+    let check = &mut check[..len];
+    for (converted, index) in requested.iter_mut().zip(indices.iter().copied().flatten()) {
+        *converted = index.to_index();
+    }
+    check.copy_from_slice(requested);
+    check_indices(requested, check, slice.len())?;
+    let ptr = slice.as_mut_ptr();
+    let mut rest: &'a [usize] = requested;
+    Ok(indices.map(|group| {
+        let (indices, tail) = rest.split_at(group.len());
+        rest = tail;
+        IndicesSliceMut {
+            ptr,
+            indices,
+            marker: PhantomData,
+        }
+    }))
+}
+
+/// Reusable scratch space for retrieving mutable references for runtime indices without
+/// allocating once the buffers have grown to fit the largest request. Unlike a `Vec<&mut T>`, it
+/// holds no references, so it can be kept across calls, e.g. in the state of an audio callback.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone)]
+pub struct IndicesScratch {
+    indices: Vec<usize>,
+    check: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl IndicesScratch {
+    #[inline]
+    pub const fn new() -> Self {
+        IndicesScratch {
+            indices: Vec::new(),
+            check: Vec::new(),
+        }
+    }
+
+    /// Scratch space for up to `capacity` indices per request without allocating.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        IndicesScratch {
+            indices: Vec::with_capacity(capacity),
+            check: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The number of indices a request may have without allocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.indices.capacity().min(self.check.capacity())
+    }
+
+    /// Returns mutable references for the requested indices in the provided slice.
+    /// Panics if any index is out of bounds or duplicated. See `indices_slice`.
+    #[inline]
+    pub fn indices_slice<'a, T, I: IndexType>(
+        &'a mut self,
+        slice: &'a mut [T],
+        indices: &[I],
+    ) -> IndicesSliceMut<'a, T> {
+        match self.try_indices_slice(slice, indices) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references for the requested indices in the provided slice.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. See
+    /// `try_indices_slice`.
+    pub fn try_indices_slice<'a, T, I: IndexType>(
+        &'a mut self,
+        slice: &'a mut [T],
+        indices: &[I],
+    ) -> Result<IndicesSliceMut<'a, T>, TryIndicesError> {
+        self.indices.clear();
+        self.indices
+            .extend(indices.iter().map(|index| index.to_index()));
+        self.check_indices(slice.len())?;
+        Ok(IndicesSliceMut {
+            ptr: slice.as_mut_ptr(),
+            indices: &self.indices,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns mutable references for each group of requested indices.
+    /// Panics if any index is out of bounds or duplicated across all groups. See `indices_slices`.
+    #[inline]
+    pub fn indices_slices<'a, T, const N: usize>(
+        &'a mut self,
+        slice: &'a mut [T],
+        indices: [&[usize]; N],
+    ) -> [IndicesSliceMut<'a, T>; N] {
+        match self.try_indices_slices(slice, indices) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references for each group of requested indices.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. Positions reported in
+    /// the error count across all the requested groups, in order. See `try_indices_slices`.
+    pub fn try_indices_slices<'a, T, const N: usize>(
+        &'a mut self,
+        slice: &'a mut [T],
+        indices: [&[usize]; N],
+    ) -> Result<[IndicesSliceMut<'a, T>; N], TryIndicesError> {
+        self.indices.clear();
+        for group in indices {
+            self.indices.extend_from_slice(group);
+        }
+        self.check_indices(slice.len())?;
+        let ptr = slice.as_mut_ptr();
+        let mut rest = self.indices.as_slice();
+        Ok(indices.map(|group| {
+            let (indices, tail) = rest.split_at(group.len());
+            rest = tail;
+            IndicesSliceMut {
+                ptr,
+                indices,
+                marker: PhantomData,
+            }
+        }))
+    }
+
+    fn check_indices(&mut self, len: usize) -> Result<(), TryIndicesError> {
+        self.check.clear();
+        self.check.extend_from_slice(&self.indices);
+        check_indices(&self.indices, &mut self.check, len)
+    }
+}
+
+/// Mutable references for validated indices of a slice, claimed as they are accessed. Created by
+/// `indices_slice_with`, `indices_slices_with` and `IndicesScratch`.
+pub struct IndicesSliceMut<'a, T> {
+    ptr: *mut T,
+    /// Distinct and in bounds.
+    indices: &'a [usize],
+    marker: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<T: Send> Send for IndicesSliceMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IndicesSliceMut<'_, T> {}
+
+impl<'a, T> IndicesSliceMut<'a, T> {
+    /// The requested indices.
+    #[inline]
+    pub fn indices(&self) -> &'a [usize] {
+        self.indices
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns a mutable reference to the element of the index requested at `position`, or `None`
+    /// if `position` is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, position: usize) -> Option<&mut T> {
+        let index = *self.indices.get(position)?;
+        Some(unsafe { &mut *self.ptr.add(index) })
+    }
+
+    /// Returns an iterator over mutable references to the elements, in the order requested.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IndicesSliceIter<'_, T> {
+        IndicesSliceIter {
+            ptr: self.ptr,
+            indices: self.indices.iter(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for IndicesSliceMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IndicesSliceIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IndicesSliceIter {
+            ptr: self.ptr,
+            indices: self.indices.iter(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b mut IndicesSliceMut<'a, T> {
+    type Item = &'b mut T;
+    type IntoIter = IndicesSliceIter<'b, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for IndicesSliceMut<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.indices
+                    .iter()
+                    .map(|index| unsafe { &*self.ptr.add(*index) }),
+            )
+            .finish()
+    }
+}
+
+/// Iterator over the mutable references of an `IndicesSliceMut`.
+pub struct IndicesSliceIter<'a, T> {
+    ptr: *mut T,
+    indices: core::slice::Iter<'a, usize>,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<T: Send> Send for IndicesSliceIter<'_, T> {}
+unsafe impl<T: Sync> Sync for IndicesSliceIter<'_, T> {}
+
+impl<'a, T> Iterator for IndicesSliceIter<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        // Each index is distinct and in bounds, and is yielded at most once.
+        let index = *self.indices.next()?;
+        Some(unsafe { &mut *self.ptr.add(index) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IndicesSliceIter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = *self.indices.next_back()?;
+        Some(unsafe { &mut *self.ptr.add(index) })
+    }
+}
+
+impl<T> ExactSizeIterator for IndicesSliceIter<'_, T> {}
+
+impl<T> FusedIterator for IndicesSliceIter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use crate::IndicesScratch;
+    use crate::{
+        indices_slice_with, indices_slices_with, try_indices_slice_with, try_indices_slices_with,
+        TryIndicesError,
+    };
+
+    #[test]
+    fn slice_with_scratch() {
+        let mut data = [0, 1, 2, 3, 4, 5];
+        let mut scratch = [0; 8];
+        let indices = [5u32, 1, 3];
+        let mut elements = indices_slice_with(&mut data, &indices, &mut scratch);
+        assert_eq!(elements.len(), 3);
+        *elements.get_mut(0).unwrap() += 10;
+        assert!(elements.get_mut(3).is_none());
+        for element in &mut elements {
+            *element += 1;
+        }
+        let mut iter = elements.into_iter();
+        let (five, one, three) = (
+            iter.next().unwrap(),
+            iter.next().unwrap(),
+            iter.next().unwrap(),
+        );
+        assert!(iter.next().is_none());
+        core::mem::swap(five, one);
+        *three = 0;
+        assert_eq!(data, [0, 16, 2, 0, 4, 2]);
+        assert_eq!(
+            try_indices_slice_with(&mut data, &[1usize, 2, 1], &mut scratch).map(|_| ()),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[should_panic(
+        expected = "Scratch space of length `3` is shorter than twice the `2` requested indices."
+    )]
+    #[test]
+    fn slice_with_short_scratch() {
+        let _elements = indices_slice_with(&mut [0, 1], &[0usize, 1], &mut [0; 3]);
+    }
+
+    #[test]
+    fn slices_with_scratch() {
+        let mut data = [0, 1, 2, 3, 4];
+        let mut scratch = [0; 6];
+        let [mut first, second, third] =
+            indices_slices_with(&mut data, [&[4u8, 0][..], &[], &[2]], &mut scratch);
+        assert!(second.is_empty());
+        assert_eq!(third.indices(), &[2]);
+        for element in third {
+            *element += 10;
+        }
+        *first.get_mut(1).unwrap() = 7;
+        assert_eq!(data, [7, 1, 12, 3, 4]);
+        assert_eq!(
+            try_indices_slices_with(&mut data, [&[1usize][..], &[3, 1]], &mut scratch).map(|_| ()),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn scratch_is_reused() {
+        let mut scratch = IndicesScratch::with_capacity(64);
+        let capacity = scratch.capacity();
+        let mut data: Vec<usize> = (0..100).collect();
+        for round in 0..3 {
+            let indices: Vec<u32> = (0..64).rev().map(|index| index + round).collect();
+            for element in scratch.indices_slice(&mut data, &indices) {
+                *element += 1;
+            }
+        }
+        assert_eq!(scratch.capacity(), capacity);
+        assert_eq!(data[..4], [1, 3, 5, 6]);
+        assert_eq!(
+            scratch
+                .try_indices_slice(&mut data, &[99usize, 100])
+                .map(|_| ()),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 100,
+                position: 1,
+                len: 100
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn scratch_slices() {
+        let mut scratch = IndicesScratch::new();
+        let mut data = [0, 1, 2, 3, 4];
+        let [mut first, second, third] = scratch.indices_slices(&mut data, [&[4, 0], &[], &[2]]);
+        assert!(second.is_empty());
+        assert_eq!(third.indices(), &[2]);
+        for element in third {
+            *element += 10;
+        }
+        *first.get_mut(1).unwrap() = 7;
+        assert_eq!(data, [7, 1, 12, 3, 4]);
+        assert_eq!(
+            scratch
+                .try_indices_slices(&mut data, [&[1], &[3, 1]])
+                .map(|_| ()),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 1,
+                first: 0,
+                second: 2
+            })
+        );
+    }
+}
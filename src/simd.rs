@@ -0,0 +1,210 @@
+//! Passes over large requests that are vectorised on x86_64. The AVX2 versions are picked at
+//! runtime with the `std` feature, or at compile time with `-C target-feature=+avx2` without it.
+//! Every other target uses the [`scalar`] versions, which give the same results.
+
+// `max_index` is only used by the bitset, which needs `alloc`.
+#![cfg_attr(not(feature = "alloc"), allow(dead_code))]
+
+/// The largest index, or `0` if there are none.
+#[inline]
+pub fn max_index(indices: &[usize]) -> usize {
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    if has_avx2() {
+        return unsafe { avx2::max_index(indices) };
+    }
+    scalar::max_index(indices)
+}
+
+/// Whether every index is smaller than the next.
+#[inline]
+pub fn is_strictly_increasing(indices: &[usize]) -> bool {
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    if has_avx2() {
+        return unsafe { avx2::is_strictly_increasing(indices) };
+    }
+    scalar::is_strictly_increasing(indices)
+}
+
+/// The first index that is equal to the next, which for sorted indices is the smallest duplicate.
+#[inline]
+pub fn find_adjacent_equal(indices: &[usize]) -> Option<usize> {
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    if has_avx2() {
+        return unsafe { avx2::find_adjacent_equal(indices) };
+    }
+    scalar::find_adjacent_equal(indices)
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[inline]
+fn has_avx2() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "avx2")
+    }
+}
+
+/// The fallbacks for targets without AVX2.
+pub mod scalar {
+    /// See [`super::max_index`].
+    #[inline]
+    pub fn max_index(indices: &[usize]) -> usize {
+        indices.iter().copied().max().unwrap_or(0)
+    }
+
+    /// See [`super::is_strictly_increasing`].
+    #[inline]
+    pub fn is_strictly_increasing(indices: &[usize]) -> bool {
+        indices.windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    /// See [`super::find_adjacent_equal`].
+    #[inline]
+    pub fn find_adjacent_equal(indices: &[usize]) -> Option<usize> {
+        indices
+            .windows(2)
+            .find(|pair| pair[0] == pair[1])
+            .map(|pair| pair[0])
+    }
+}
+
+/// AVX2 has no unsigned 64 bit comparison, so indices are compared as signed after flipping their
+/// sign bit.
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+mod avx2 {
+    use core::arch::x86_64::*;
+
+    const LANES: usize = 4;
+
+    #[inline(always)]
+    unsafe fn load_biased(indices: &[usize], at: usize, sign: __m256i) -> __m256i {
+        debug_assert!(at + LANES <= indices.len());
+        _mm256_xor_si256(_mm256_loadu_si256(indices.as_ptr().add(at).cast()), sign)
+    }
+
+    /// # Safety
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn max_index(indices: &[usize]) -> usize {
+        let sign = _mm256_set1_epi64x(i64::MIN);
+        // Two accumulators, so consecutive comparisons do not wait on each other.
+        let mut first = sign;
+        let mut second = sign;
+        let mut at = 0;
+        while at + 2 * LANES <= indices.len() {
+            let one = load_biased(indices, at, sign);
+            let two = load_biased(indices, at + LANES, sign);
+            first = _mm256_blendv_epi8(first, one, _mm256_cmpgt_epi64(one, first));
+            second = _mm256_blendv_epi8(second, two, _mm256_cmpgt_epi64(two, second));
+            at += 2 * LANES;
+        }
+        let max = _mm256_blendv_epi8(first, second, _mm256_cmpgt_epi64(second, first));
+        let mut lanes = [0u64; LANES];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), _mm256_xor_si256(max, sign));
+        let max = lanes.iter().copied().max().unwrap_or(0) as usize;
+        indices[at..].iter().copied().fold(max, usize::max)
+    }
+
+    /// # Safety
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn is_strictly_increasing(indices: &[usize]) -> bool {
+        let sign = _mm256_set1_epi64x(i64::MIN);
+        let mut at = 0;
+        // Compares `indices[at..at + 4]` with `indices[at + 1..at + 5]`.
+        while at + LANES < indices.len() {
+            let current = load_biased(indices, at, sign);
+            let next = load_biased(indices, at + 1, sign);
+            if _mm256_movemask_epi8(_mm256_cmpgt_epi64(next, current)) != -1 {
+                return false;
+            }
+            at += LANES;
+        }
+        super::scalar::is_strictly_increasing(&indices[at..])
+    }
+
+    /// # Safety
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_adjacent_equal(indices: &[usize]) -> Option<usize> {
+        let mut at = 0;
+        while at + LANES < indices.len() {
+            let current = _mm256_loadu_si256(indices.as_ptr().add(at).cast());
+            let next = _mm256_loadu_si256(indices.as_ptr().add(at + 1).cast());
+            if _mm256_movemask_epi8(_mm256_cmpeq_epi64(current, next)) != 0 {
+                return super::scalar::find_adjacent_equal(&indices[at..=at + LANES]);
+            }
+            at += LANES;
+        }
+        super::scalar::find_adjacent_equal(&indices[at..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs of every length up to a few vectors, with the interesting element at each position.
+    fn each_position(mut f: impl FnMut(&[usize], usize)) {
+        for len in 0..20 {
+            let increasing: Vec<usize> = (0..len).map(|i| i * 3 + 1).collect();
+            for position in 0..len {
+                f(&increasing, position);
+            }
+            f(&increasing, len);
+        }
+    }
+
+    #[test]
+    fn max_index_matches_scalar() {
+        assert_eq!(max_index(&[]), 0);
+        each_position(|increasing, position| {
+            let mut indices = increasing.to_vec();
+            if position < indices.len() {
+                indices[position] = usize::MAX - position;
+            }
+            indices.reverse();
+            assert_eq!(max_index(&indices), scalar::max_index(&indices));
+            assert_eq!(max_index(increasing), scalar::max_index(increasing));
+        });
+        let high = usize::MAX / 2 + 1;
+        assert_eq!(max_index(&[high, 5, high + 1, 0, 0]), high + 1);
+    }
+
+    #[test]
+    fn is_strictly_increasing_matches_scalar() {
+        each_position(|increasing, position| {
+            assert!(is_strictly_increasing(increasing));
+            let mut indices = increasing.to_vec();
+            if position + 1 < indices.len() {
+                indices[position + 1] = indices[position];
+                assert!(!is_strictly_increasing(&indices));
+                indices[position + 1] = 0;
+                assert!(!is_strictly_increasing(&indices));
+            }
+        });
+        // Indices with the top bit set are compared as unsigned.
+        let high = usize::MAX / 2 + 1;
+        assert!(is_strictly_increasing(&[0, 1, 2, high, usize::MAX]));
+        assert!(!is_strictly_increasing(&[0, 1, 2, usize::MAX, high]));
+    }
+
+    #[test]
+    fn find_adjacent_equal_matches_scalar() {
+        each_position(|increasing, position| {
+            assert_eq!(find_adjacent_equal(increasing), None);
+            let mut indices = increasing.to_vec();
+            if position + 1 < indices.len() {
+                indices[position + 1] = indices[position];
+                if position + 3 < indices.len() {
+                    indices[position + 3] = indices[position + 2];
+                }
+                assert_eq!(find_adjacent_equal(&indices), Some(indices[position]));
+            }
+        });
+    }
+}
@@ -0,0 +1,115 @@
+/// Splits an array into mutable references to its first `A` elements, the `B` elements after
+/// them, and the rest, e.g. `split_array_mut::<_, 4, 2, 8>(&mut packet)` for a 4 byte header, a 2
+/// byte length and the payload. Fails to compile if `A + B > N`, and performs no runtime checks.
+///
+/// ```compile_fail
+/// let mut packet = [0u8; 6];
+/// let (header, length, payload) = indices::split_array_mut::<_, 4, 4, 6>(&mut packet);
+/// ```
+#[inline]
+pub fn split_array_mut<T, const A: usize, const B: usize, const N: usize>(
+    array: &mut [T; N],
+) -> (&mut [T; A], &mut [T; B], &mut [T]) {
+    const {
+        assert!(
+            A <= N && B <= N - A,
+            "The split arrays are longer than the array."
+        )
+    };
+    let ptr = array.as_mut_ptr();
+    // The three parts are in bounds and do not overlap.
+    unsafe {
+        (
+            &mut *ptr.cast::<[T; A]>(),
+            &mut *ptr.add(A).cast::<[T; B]>(),
+            core::slice::from_raw_parts_mut(ptr.add(A + B), N - A - B),
+        )
+    }
+}
+
+/// Returns mutable references to the `A` elements starting at `START_A` and the `B` elements
+/// starting at `START_B` of an array, e.g. two blocks of a row-major matrix. Fails to compile if
+/// either sub-array is out of bounds or they overlap, and performs no runtime checks.
+///
+/// ```compile_fail
+/// let mut matrix = [0; 16];
+/// let (first, second) = indices::sub_arrays_mut::<_, 0, 8, 4, 8, 16>(&mut matrix);
+/// ```
+///
+/// ```compile_fail
+/// let mut matrix = [0; 16];
+/// let (first, second) = indices::sub_arrays_mut::<_, 0, 8, 12, 8, 16>(&mut matrix);
+/// ```
+#[inline]
+pub fn sub_arrays_mut<
+    T,
+    const START_A: usize,
+    const A: usize,
+    const START_B: usize,
+    const B: usize,
+    const N: usize,
+>(
+    array: &mut [T; N],
+) -> (&mut [T; A], &mut [T; B]) {
+    const {
+        assert!(
+            START_A <= N && A <= N - START_A && START_B <= N && B <= N - START_B,
+            "A sub-array is out of bounds of the array."
+        );
+        assert!(
+            A == 0 || B == 0 || START_A + A <= START_B || START_B + B <= START_A,
+            "The sub-arrays overlap."
+        )
+    };
+    let ptr = array.as_mut_ptr();
+    // Both sub-arrays are in bounds and do not overlap.
+    unsafe {
+        (
+            &mut *ptr.add(START_A).cast::<[T; A]>(),
+            &mut *ptr.add(START_B).cast::<[T; B]>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{split_array_mut, sub_arrays_mut};
+
+    #[test]
+    fn split_packet() {
+        let mut packet = [0u8; 10];
+        let (header, length, payload) = split_array_mut::<_, 4, 2, 10>(&mut packet);
+        *header = *b"PKT1";
+        *length = (payload.len() as u16).to_be_bytes();
+        payload.fill(7);
+        assert_eq!(packet, [b'P', b'K', b'T', b'1', 0, 4, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn split_whole_and_empty() {
+        let mut data = [1, 2, 3];
+        let (all, none, rest) = split_array_mut::<_, 3, 0, 3>(&mut data);
+        assert_eq!((all, none, rest), (&mut [1, 2, 3], &mut [], &mut [][..]));
+        let (none, all, rest): (&mut [i32; 0], &mut [i32; 3], _) = split_array_mut(&mut data);
+        all[0] = 0;
+        assert!(none.is_empty() && rest.is_empty());
+        let mut empty: [i32; 0] = [];
+        let (_, _, rest) = split_array_mut::<_, 0, 0, 0>(&mut empty);
+        assert!(rest.is_empty());
+        assert_eq!(data, [0, 2, 3]);
+    }
+
+    #[test]
+    fn matrix_blocks() {
+        // A 4x4 matrix, row-major, as two 2x4 row blocks.
+        let mut matrix: [i32; 16] = core::array::from_fn(|i| i as i32);
+        let (bottom, top) = sub_arrays_mut::<_, 8, 8, 0, 8, 16>(&mut matrix);
+        core::mem::swap(bottom, top);
+        assert_eq!(matrix[..4], [8, 9, 10, 11]);
+        let (first, last) = sub_arrays_mut::<_, 0, 1, 15, 1, 16>(&mut matrix);
+        core::mem::swap(&mut first[0], &mut last[0]);
+        assert_eq!((matrix[0], matrix[15]), (7, 8));
+        let (empty, _) = sub_arrays_mut::<_, 3, 0, 2, 4, 16>(&mut matrix);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::check::check_indices;
+use crate::{TryIndicesError, TryStencilError};
+
+/// How a `Stencil` handles neighbours that are off the edge of the grid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EdgePolicy {
+    /// The neighbour is `None`.
+    Skip,
+    /// The neighbour is the nearest element on the edge.
+    Clamp,
+    /// The neighbour wraps around to the opposite edge.
+    Wrap,
+    /// The stencil returns `TryStencilError::OffEdge`.
+    Error,
+}
+
+/// Fixed relative offsets that are applied at a centre index to retrieve mutable references for the
+/// centre's neighbours, e.g. `Stencil::line([-1, 0, 1], EdgePolicy::Wrap)`.
+///
+/// Neighbours are returned in the order of the offsets. With `EdgePolicy::Clamp` or
+/// `EdgePolicy::Wrap`, two offsets may reach the same element near an edge, which is reported as a
+/// duplicate like any other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Stencil<const N: usize> {
+    /// `None` for a line of the whole slice.
+    columns: Option<usize>,
+    /// `[row, column]` offsets.
+    offsets: [[isize; 2]; N],
+    edge: EdgePolicy,
+}
+
+impl<const N: usize> Stencil<N> {
+    /// A stencil over the slice as a single line.
+    pub const fn line(offsets: [isize; N], edge: EdgePolicy) -> Self {
+        let mut grid_offsets = [[0; 2]; N];
+        let mut i = 0;
+        while i < N {
+            grid_offsets[i][1] = offsets[i];
+            i += 1;
+        }
+        Stencil {
+            columns: None,
+            offsets: grid_offsets,
+            edge,
+        }
+    }
+
+    /// A stencil over the slice as a row-major grid with `columns` columns, i.e. a row stride of
+    /// `columns`. Offsets are `[row, column]`.
+    pub const fn grid(columns: usize, offsets: [[isize; 2]; N], edge: EdgePolicy) -> Self {
+        Stencil {
+            columns: Some(columns),
+            offsets,
+            edge,
+        }
+    }
+
+    /// Returns mutable references for the neighbours of `centre`. Neighbours are only `None` when
+    /// off the edge with `EdgePolicy::Skip`.
+    /// Panics if the grid does not match the slice, if the centre is out of bounds, if a neighbour
+    /// is off the edge with `EdgePolicy::Error`, or if two offsets reach the same element.
+    pub fn apply<'a, T>(&self, slice: &'a mut [T], centre: usize) -> [Option<&'a mut T>; N] {
+        match self.try_apply(slice, centre) {
+            Ok(result) => result,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns mutable references for the neighbours of `centre`. Neighbours are only `None` when
+    /// off the edge with `EdgePolicy::Skip`.
+    /// Returns `TryStencilError` if the grid does not match the slice, if the centre is out of
+    /// bounds, if a neighbour is off the edge with `EdgePolicy::Error`, or if two offsets reach the
+    /// same element.
+    pub fn try_apply<'a, T>(
+        &self,
+        slice: &'a mut [T],
+        centre: usize,
+    ) -> Result<[Option<&'a mut T>; N], TryStencilError> {
+        let len = slice.len();
+        let (rows, columns) = match self.columns {
+            None => (1, len),
+            Some(columns) if len.checked_rem(columns) == Some(0) => (len / columns, columns),
+            Some(columns) => return Err(TryStencilError::ShapeMismatch { columns, len }),
+        };
+        if centre >= len {
+            return Err(TryStencilError::CentreOutOfBounds { centre, len });
+        }
+        let (row, column) = (centre / columns, centre % columns);
+        let mut resolved: [Option<usize>; N] = [None; N];
+        let mut indices = [0; N];
+        let mut positions = [0; N];
+        let mut count = 0;
+        for (position, [row_offset, column_offset]) in self.offsets.iter().enumerate() {
+            let neighbour = self.resolve(row, *row_offset, rows).zip(self.resolve(
+                column,
+                *column_offset,
+                columns,
+            ));
+            match neighbour {
+                Some((row, column)) => {
+                    let index = row * columns + column;
+                    resolved[position] = Some(index);
+                    indices[count] = index;
+                    positions[count] = position;
+                    count += 1;
+                }
+                None if self.edge == EdgePolicy::Error => {
+                    return Err(TryStencilError::OffEdge { centre, position });
+                }
+                None => {}
+            }
+        }
+        let mut check = indices;
+        match check_indices(&indices[..count], &mut check[..count], len) {
+            Ok(()) => {}
+            Err(TryIndicesError::DuplicateIndex {
+                index,
+                first,
+                second,
+            }) => {
+                return Err(TryStencilError::DuplicateIndex {
+                    index,
+                    first: positions[first],
+                    second: positions[second],
+                });
+            }
+            Err(error) => unreachable!("Neighbours were resolved within the grid: {}", error),
+        }
+        let ptr = slice.as_mut_ptr();
+        Ok(resolved.map(|index| index.map(|index| unsafe { &mut *ptr.add(index) })))
+    }
+
+    /// Resolves a coordinate moved by `offset` on an axis of `size` elements, or `None` if it is off
+    /// the edge and not clamped or wrapped.
+    #[inline]
+    fn resolve(&self, coordinate: usize, offset: isize, size: usize) -> Option<usize> {
+        match coordinate.checked_add_signed(offset) {
+            Some(moved) if moved < size => Some(moved),
+            _ => match self.edge {
+                EdgePolicy::Skip | EdgePolicy::Error => None,
+                EdgePolicy::Clamp if offset < 0 => Some(0),
+                EdgePolicy::Clamp => Some(size - 1),
+                EdgePolicy::Wrap => {
+                    Some((coordinate as i128 + offset as i128).rem_euclid(size as i128) as usize)
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EdgePolicy, Stencil, TryStencilError};
+
+    #[test]
+    fn line_wrap() {
+        let mut data = [0, 1, 2, 3, 4];
+        let stencil = Stencil::line([-1, 0, 1], EdgePolicy::Wrap);
+        let [left, centre, right] = stencil.apply(&mut data, 0);
+        assert_eq!(
+            (left.as_deref(), centre.as_deref(), right.as_deref()),
+            (Some(&4), Some(&0), Some(&1))
+        );
+        *centre.unwrap() = *left.unwrap() + *right.unwrap();
+        assert_eq!(data, [5, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn line_skip() {
+        let mut data = [0, 1, 2];
+        let stencil = Stencil::line([-1, 0, 1], EdgePolicy::Skip);
+        let [left, centre, right] = stencil.apply(&mut data, 2);
+        assert_eq!((left, centre, right), (Some(&mut 1), Some(&mut 2), None));
+    }
+
+    #[test]
+    fn line_clamp_duplicate() {
+        let mut data = [0, 1, 2];
+        let stencil = Stencil::line([-1, 0, 1], EdgePolicy::Clamp);
+        assert_eq!(
+            stencil.try_apply(&mut data, 0),
+            Err(TryStencilError::DuplicateIndex {
+                index: 0,
+                first: 0,
+                second: 1
+            })
+        );
+        let stencil = Stencil::line([-2, 2], EdgePolicy::Clamp);
+        let [left, right] = stencil.apply(&mut data, 1);
+        assert_eq!((left, right), (Some(&mut 0), Some(&mut 2)));
+    }
+
+    #[test]
+    fn grid_von_neumann() {
+        // 3 x 4 grid.
+        let mut grid = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+        let stencil = Stencil::grid(
+            4,
+            [[0, 0], [-1, 0], [1, 0], [0, -1], [0, 1]],
+            EdgePolicy::Skip,
+        );
+        let [centre, up, down, left, right] = stencil.apply(&mut grid, 5);
+        assert_eq!(
+            [up, down, left, right].map(|neighbour| neighbour.copied()),
+            [Some(1), Some(9), Some(4), Some(6)]
+        );
+        *centre.unwrap() = 0;
+        let [_, up, down, left, right] = stencil.apply(&mut grid, 3);
+        assert_eq!(
+            [up, down, left, right].map(|neighbour| neighbour.copied()),
+            [None, Some(7), Some(2), None]
+        );
+        let stencil = Stencil::grid(4, [[-1, 0], [0, 1]], EdgePolicy::Wrap);
+        let [up, right] = stencil.apply(&mut grid, 3);
+        assert_eq!((up.copied(), right.copied()), (Some(11), Some(0)));
+    }
+
+    #[test]
+    fn try_apply_errors() {
+        let mut grid = [0; 12];
+        let stencil = Stencil::grid(5, [[0, 0]], EdgePolicy::Skip);
+        assert_eq!(
+            stencil.try_apply(&mut grid, 0),
+            Err(TryStencilError::ShapeMismatch {
+                columns: 5,
+                len: 12
+            })
+        );
+        let stencil = Stencil::grid(4, [[0, 0], [1, 1]], EdgePolicy::Error);
+        assert_eq!(
+            stencil.try_apply(&mut grid, 12),
+            Err(TryStencilError::CentreOutOfBounds {
+                centre: 12,
+                len: 12
+            })
+        );
+        assert_eq!(
+            stencil.try_apply(&mut grid, 7),
+            Err(TryStencilError::OffEdge {
+                centre: 7,
+                position: 1
+            })
+        );
+        assert!(stencil.try_apply(&mut grid, 6).is_ok());
+    }
+
+    #[should_panic(
+        expected = "Neighbour off the edge. The offset at position `0` leaves the grid from centre `0`."
+    )]
+    #[test]
+    fn apply_off_edge_message() {
+        let mut data = [0, 1, 2];
+        let _neighbours = Stencil::line([-1], EdgePolicy::Error).apply(&mut data, 0);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{indices_array_typed, try_indices_array_typed, IndexType, TryIndicesError};
+#[cfg(feature = "alloc")]
+use crate::{indices_slice_typed, try_indices_slice_typed};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// A mutable slice that is only indexed by `I`, e.g. `TypedSlice<NodeId, Node>`, so an id of
+/// another type is a compile error instead of a wrong element.
+#[derive(Debug)]
+pub struct TypedSlice<'a, I, T> {
+    elements: &'a mut [T],
+    marker: PhantomData<fn(I) -> I>,
+}
+
+impl<'a, I: IndexType, T> TypedSlice<'a, I, T> {
+    #[inline]
+    pub fn new(elements: &'a mut [T]) -> Self {
+        TypedSlice {
+            elements,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, index: I) -> Option<&T> {
+        self.elements.get(index.to_index())
+    }
+
+    /// Returns a mutable reference to the element at `index`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: I) -> Option<&mut T> {
+        self.elements.get_mut(index.to_index())
+    }
+
+    /// Returns mutable references for the requested indices.
+    /// Panics if any index is out of bounds or duplicated. See `indices_array_typed`.
+    #[inline]
+    pub fn get_indices_mut<const N: usize>(&mut self, indices: [I; N]) -> [&mut T; N] {
+        indices_array_typed(self.elements, &indices)
+    }
+
+    /// Returns mutable references for the requested indices.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_array_typed`.
+    #[inline]
+    pub fn try_get_indices_mut<const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Result<[&mut T; N], TryIndicesError> {
+        try_indices_array_typed(self.elements, &indices)
+    }
+
+    /// Returns mutable references for a number of indices only known at runtime.
+    /// Panics if any index is out of bounds or duplicated. See `indices_slice_typed`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn get_indices_slice_mut(&mut self, indices: &[I]) -> Vec<&mut T> {
+        indices_slice_typed(self.elements, indices)
+    }
+
+    /// Returns mutable references for a number of indices only known at runtime.
+    /// Returns `TryIndicesError` if any index is out of bounds or duplicated. See `try_indices_slice_typed`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn try_get_indices_slice_mut(
+        &mut self,
+        indices: &[I],
+    ) -> Result<Vec<&mut T>, TryIndicesError> {
+        try_indices_slice_typed(self.elements, indices)
+    }
+
+    /// Returns the underlying slice, which is indexed by `usize`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.elements
+    }
+
+    /// Returns the underlying slice with the lifetime of the wrapper.
+    #[inline]
+    pub fn into_slice(self) -> &'a mut [T] {
+        self.elements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, try_indices, IndexType, TryIndicesError, TypedSlice};
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    struct NodeId(u32);
+
+    impl IndexType for NodeId {
+        fn to_index(self) -> usize {
+            self.0.to_index()
+        }
+    }
+
+    #[test]
+    fn typed_ids_in_macros() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        let (one, three, rest) = indices!(slice, NodeId(1), NodeId(3), 4..);
+        core::mem::swap(one, three);
+        rest[0] = 0;
+        assert_eq!(data, [5, 2, 3, 4, 0]);
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 0, NodeId(0)),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 0,
+                first: 0,
+                second: 1
+            })
+        );
+        let slice = data.as_mut_slice();
+        let (one, two, three, four) = (1u8, 2u16, 3u64, 4u128);
+        assert!(try_indices!(slice, one, two, three, four).is_ok());
+    }
+
+    #[test]
+    fn indices_beyond_usize_are_out_of_bounds() {
+        let mut data = [5, 4, 3, 2, 1];
+        let slice = data.as_mut_slice();
+        assert_eq!(
+            try_indices!(slice, 1, u128::MAX),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: usize::MAX,
+                position: 1,
+                len: 5
+            })
+        );
+    }
+
+    #[test]
+    fn typed_slice() {
+        let mut data = [5, 4, 3, 2, 1];
+        let mut nodes: TypedSlice<NodeId, i32> = TypedSlice::new(&mut data);
+        let [four, zero] = nodes.get_indices_mut([NodeId(4), NodeId(0)]);
+        core::mem::swap(four, zero);
+        assert_eq!(nodes.get(NodeId(0)), Some(&1));
+        assert_eq!(nodes.get_mut(NodeId(5)), None);
+        assert_eq!(
+            nodes.try_get_indices_mut([NodeId(2), NodeId(2)]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 2,
+                first: 0,
+                second: 1
+            })
+        );
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes.into_slice(), &mut [1, 4, 3, 2, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn typed_slice_runtime_indices() {
+        let mut data = [5, 4, 3, 2, 1];
+        let mut nodes = TypedSlice::new(&mut data);
+        let ids = [NodeId(3), NodeId(1)];
+        for node in nodes.get_indices_slice_mut(&ids) {
+            *node = 0;
+        }
+        assert_eq!(
+            nodes.try_get_indices_slice_mut(&[NodeId(7)]),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 7,
+                position: 0,
+                len: 5
+            })
+        );
+        assert_eq!(data, [5, 0, 3, 0, 1]);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::check::check_indices;
+use crate::TryIndicesError;
+use alloc::collections::VecDeque;
+
+/// Returns mutable references for the requested logical indices in the provided `VecDeque`.
+/// Unlike `make_contiguous`, no elements are moved.
+/// Panics if any index is out of bounds or duplicate indices.
+pub fn indices_vec_deque<'a, T, const N: usize>(
+    deque: &'a mut VecDeque<T>,
+    indices: &[usize; N],
+) -> [&'a mut T; N] {
+    match try_indices_vec_deque(deque, indices) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Returns mutable references for the requested logical indices in the provided `VecDeque`.
+/// Unlike `make_contiguous`, no elements are moved.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
+pub fn try_indices_vec_deque<'a, T, const N: usize>(
+    deque: &'a mut VecDeque<T>,
+    indices: &[usize; N],
+) -> Result<[&'a mut T; N], TryIndicesError> {
+    let mut check = *indices;
+    check_indices(indices, &mut check, deque.len())?;
+    let (front, back) = deque.as_mut_slices();
+    let front_len = front.len();
+    let front_ptr = front.as_mut_ptr();
+    let back_ptr = back.as_mut_ptr();
+    Ok(core::array::from_fn(|i| {
+        let index = indices[i];
+        unsafe {
+            if index < front_len {
+                &mut *front_ptr.add(index)
+            } else {
+                &mut *back_ptr.add(index - front_len)
+            }
+        }
+    }))
+}
+
+/// Returns mutable references for the requested logical indices of a `VecDeque`.
+/// Panics if any index is out of bounds or duplicated.
+#[macro_export]
+macro_rules! indices_vec_deque {
+    ($deque:expr, $( $index:expr ),+) => {{
+        let mut iter = $crate::indices_vec_deque($deque, &[$($index),+]).into_iter();
+        ($($crate::next_of!(iter, $index),)+)
+    }};
+}
+
+/// Returns mutable references for the requested logical indices of a `VecDeque`.
+/// Returns `TryIndicesError` if any index is out of bounds or duplicated.
+#[macro_export]
+macro_rules! try_indices_vec_deque {
+    ($deque:expr, $( $index:expr ),+) => {{
+        $crate::try_indices_vec_deque($deque, &[$($index),+]).map(|array| {
+            let mut iter = array.into_iter();
+            ($($crate::next_of!(iter, $index),)+)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices_vec_deque, try_indices_vec_deque, TryIndicesError};
+    use std::collections::VecDeque;
+
+    /// A deque whose storage wraps around, so `[10, 11, 12, 13, 14]` is split across both halves.
+    fn wrapped() -> VecDeque<i32> {
+        let mut deque = VecDeque::with_capacity(5);
+        deque.extend([12, 13, 14]);
+        deque.push_front(11);
+        deque.push_front(10);
+        let (front, back) = deque.as_slices();
+        assert!(!front.is_empty() && !back.is_empty());
+        deque
+    }
+
+    #[test]
+    fn indices_vec_deque_works() {
+        let mut deque = wrapped();
+        let [four, zero, two] = indices_vec_deque(&mut deque, &[4, 0, 2]);
+        assert_eq!((*four, *zero, *two), (14, 10, 12));
+        *four = 40;
+        *zero = 0;
+        *two = 20;
+        assert_eq!(deque, [0, 11, 20, 13, 40]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn indices_vec_deque_duplicate_indices() {
+        let mut deque = wrapped();
+        let [_one, _two] = indices_vec_deque(&mut deque, &[3, 3]);
+    }
+
+    #[test]
+    fn try_indices_vec_deque_errors() {
+        let mut deque = wrapped();
+        assert_eq!(
+            try_indices_vec_deque(&mut deque, &[1, 5]),
+            Err(TryIndicesError::IndexOutOfBounds {
+                index: 5,
+                position: 1,
+                len: 5
+            })
+        );
+        assert_eq!(
+            try_indices_vec_deque(&mut deque, &[4, 0, 4]),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 4,
+                first: 0,
+                second: 2
+            })
+        );
+        let mut empty: VecDeque<i32> = VecDeque::new();
+        assert_eq!(try_indices_vec_deque(&mut empty, &[]), Ok([]));
+    }
+
+    #[test]
+    fn indices_vec_deque_macro() {
+        let mut deque = wrapped();
+        let (three, one) = indices_vec_deque!(&mut deque, 3, 1);
+        std::mem::swap(three, one);
+        assert_eq!(deque, [10, 13, 12, 11, 14]);
+    }
+
+    #[test]
+    fn try_indices_vec_deque_macro() {
+        let mut deque = wrapped();
+        let (zero, four) = try_indices_vec_deque!(&mut deque, 0, 4).unwrap();
+        *zero += *four;
+        assert_eq!(deque, [24, 11, 12, 13, 14]);
+        assert_eq!(
+            try_indices_vec_deque!(&mut deque, 0, 4, 0),
+            Err(TryIndicesError::DuplicateIndex {
+                index: 0,
+                first: 0,
+                second: 2
+            })
+        );
+    }
+}
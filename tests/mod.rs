@@ -45,4 +45,26 @@ pub mod should_not_compile_tests {
         t.compile_fail("tests/trybuild/try_indices/cannot_return_local_data.rs");
         t.compile_fail("tests/trybuild/try_indices_ordered/cannot_return_local_data.rs");
     }
+
+    #[test]
+    fn no_mut_requests_when_ordered() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/indices_ordered/no_mut_requests.rs");
+    }
+
+    #[test]
+    fn unchecked_requires_unsafe() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/indices/unchecked_requires_unsafe.rs");
+    }
+
+    #[test]
+    fn checked_literal_indices() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/checked/duplicate_literal_indices.rs");
+        t.compile_fail("tests/trybuild/checked/unsorted_literal_indices.rs");
+        t.compile_fail("tests/trybuild/checked/negative_literal_indices.rs");
+        t.compile_fail("tests/trybuild/checked/decreasing_literal_range.rs");
+        t.pass("tests/trybuild/checked/runtime_arguments.rs");
+    }
 }
@@ -0,0 +1,10 @@
+use indices_macros::{indices, indices_ordered, try_indices, try_indices_ordered};
+
+fn main() {
+    let mut data = [5, 4, 3, 2, 1];
+    let slice = data.as_mut_slice();
+    let (_one, _two) = indices!(slice, 1, 1);
+    let _result = try_indices!(slice, 0, 3, 3);
+    let (_one, _two) = indices_ordered!(slice, 0..3, 2);
+    let _result = try_indices_ordered!(slice, 1, 2..=4, 4..);
+}
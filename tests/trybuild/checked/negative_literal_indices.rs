@@ -0,0 +1,8 @@
+use indices_macros::{indices, try_indices};
+
+fn main() {
+    let mut data = [5, 4, 3, 2, 1];
+    let slice = data.as_mut_slice();
+    let (_one, _two) = indices!(slice, -1, 1);
+    let _result = try_indices!(slice, 0, -2..3);
+}
@@ -0,0 +1,20 @@
+use indices::TryIndicesError;
+use indices_macros::{indices, try_indices};
+
+fn main() {
+    let mut data = [5, 4, 3, 2, 1];
+    let slice = data.as_mut_slice();
+    let i: usize = 1;
+    let (one, rest) = indices!(slice, i, 2..);
+    *one += rest[0];
+    assert_eq!(data, [5, 7, 3, 2, 1]);
+    let slice = data.as_mut_slice();
+    assert_eq!(
+        try_indices!(slice, i, 1),
+        Err(TryIndicesError::DuplicateIndex {
+            index: 1,
+            first: 0,
+            second: 1
+        })
+    );
+}
@@ -0,0 +1,10 @@
+use indices_macros::{indices, indices_ordered, try_indices_ordered};
+
+fn main() {
+    let mut data = [5, 4, 3, 2, 1];
+    let slice = data.as_mut_slice();
+    let (_three, _one) = indices!(slice, 3, 1);
+    let (_three, _one) = indices_ordered!(slice, 3, 1);
+    let i = 2;
+    let _result = try_indices_ordered!(slice, 3.., i, 0);
+}
@@ -0,0 +1,7 @@
+use indices::indices_unchecked;
+
+fn main() {
+    let mut data = [5, 4, 3, 2, 1];
+    let slice = data.as_mut_slice();
+    let (_one, _two) = indices_unchecked!(slice, 1, 3);
+}